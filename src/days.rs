@@ -1,25 +1,67 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-pub mod day19;
-pub mod day20;
-pub mod day21;
-pub mod day22;
-pub mod day23;
-pub mod day24;
-pub mod day25;
+//! Registry of every implemented day.
+//!
+//! Each `dayNN` module registers itself with a single line in the `days!` invocation below, which
+//! declares the module and adds its [`Solver`](crate::etc::solver::Solver) to [`DAYS`]. The
+//! runner only ever looks days up through this table.
+use crate::etc::solver::{self, Day};
+
+macro_rules! days {
+    ($($day:literal => $module:ident::$solver:ident,)*) => {
+        $(pub mod $module;)*
+
+        pub const DAYS: &[Day] = &[
+            $(Day { day: $day, run: solver::run::<$module::$solver> },)*
+        ];
+    };
+}
+
+days! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+    7 => day07::Day07,
+    8 => day08::Day08,
+    9 => day09::Day09,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15,
+    16 => day16::Day16,
+    17 => day17::Day17,
+    18 => day18::Day18,
+    19 => day19::Day19,
+    20 => day20::Day20,
+    21 => day21::Day21,
+    22 => day22::Day22,
+    23 => day23::Day23,
+    24 => day24::Day24,
+    25 => day25::Day25,
+}
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+pub fn available() -> String {
+    DAYS.iter()
+        .map(|d| format!("{:02}", d.day))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+        assert_eq!(get(5).map(|d| d.day), Some(5));
+        assert!(get(26).is_none());
+    }
+}
@@ -1,25 +1,25 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 
-struct Input {
+pub struct Input {
     left: Vec<u32>,
     right: Vec<u32>,
 }
 
 impl TryFrom<&str> for Input {
-    type Error = &'static str;
+    type Error = Error;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
         let (mut left, mut right): (Vec<u32>, Vec<u32>) = input
             .lines()
             .map(|line| {
-                let (left, right) = line
-                    .split_once("   ")
-                    .expect("Lines separated by triple spaces");
-                (
-                    left.parse::<u32>().expect("Strings to be numbers"),
-                    right.parse::<u32>().expect("Strings to be numbers"),
-                )
+                let (left, right) = line.split_once("   ").ok_or_else(|| {
+                    Error::on_line(input, line, "expected two numbers separated by three spaces")
+                })?;
+                Ok((error::parse(input, left)?, error::parse(input, right)?))
             })
+            .collect::<Result<Vec<(u32, u32)>, Error>>()?
+            .into_iter()
             .unzip();
 
         left.sort();
@@ -29,9 +29,22 @@ impl TryFrom<&str> for Input {
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let input = Input::try_from(input).expect("Valid input");
-    (Solution::from(p1(&input)), Solution::from(p2(&input)))
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input<'a> = Input;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        Input::try_from(input)
+    }
+
+    fn part1(input: &Input) -> Result<Solution, Error> {
+        Ok(Solution::from(p1(input)))
+    }
+
+    fn part2(input: &Input) -> Result<Solution, Error> {
+        Ok(Solution::from(p2(input)))
+    }
 }
 
 fn p1(input: &Input) -> u32 {
@@ -54,13 +67,12 @@ fn p2(input: &Input) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::etc::solution::Solution;
-
+    use super::*;
 
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day01/test.txt");
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day01::solve(input).unwrap();
 
         assert_eq!(p1, Solution::U32(11));
         assert_eq!(p2, Solution::U64(31));
@@ -1,25 +1,32 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 use itertools::Itertools;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (mut p1, mut p2) = (0, 0);
-    for line in input.lines() {
-        let values = line
-            .split_whitespace()
-            .map(str::parse::<i32>)
-            .filter_map(Result::ok)
-            .collect::<Vec<_>>();
+pub struct Day02;
 
-        if check_report_safe(&values) {
-            p1 += 1;
-        }
+impl Solver for Day02 {
+    type Input<'a> = Vec<Vec<i32>>;
 
-        if brute_force(&values) {
-            p2 += 1;
-        }
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, Error> {
+        input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|level| error::parse::<i32>(input, level))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect()
+    }
+
+    fn part1(reports: &Vec<Vec<i32>>) -> Result<Solution, Error> {
+        let p1 = reports.iter().filter(|r| check_report_safe(r)).count() as i32;
+        Ok(Solution::from(p1))
     }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(reports: &Vec<Vec<i32>>) -> Result<Solution, Error> {
+        let p2 = reports.iter().filter(|r| brute_force(r)).count() as i32;
+        Ok(Solution::from(p2))
+    }
 }
 
 fn brute_force(report: &[i32]) -> bool {
@@ -51,13 +58,12 @@ fn check_report_safe(report: &[i32]) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::etc::solution::Solution;
-
+    use super::*;
 
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day02/test.txt");
-        let (part1, part2) = super::solve(input);
+        let (part1, part2) = Day02::solve(input).unwrap();
         assert_eq!(part1, Solution::I32(2));
         assert_eq!(part2, Solution::I32(4));
     }
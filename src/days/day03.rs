@@ -1,49 +1,57 @@
-use crate::{Solution, SolutionPair};
+use crate::utils::parse::*;
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let p1 = compute(input);
+pub struct Day03;
 
-    // match regex, replace with nothing, parse, sum
-    let replacer = regex::Regex::new(r"don't\(\)[\s\S]*?do\(\)").unwrap();
+impl Solver for Day03 {
+    type Input<'a> = &'a str;
 
-    let input = replacer.replace_all(input, "").to_string();
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Solution, Error> {
+        Ok(Solution::from(compute(input)))
+    }
 
-    let p2 = match input.contains("don't()") {
-        true => {
-            let (input, _) = input.split_once("don't()").unwrap();
-            compute(input)
-        },
-        false => compute(&input),
-    };
+    fn part2(input: &&str) -> Result<Solution, Error> {
+        // match regex, replace with nothing, parse, sum
+        let replacer = regex::Regex::new(r"don't\(\)[\s\S]*?do\(\)").unwrap();
 
-    (Solution::from(p1), Solution::from(p2))
+        let input = replacer.replace_all(input, "").to_string();
+
+        let p2 = match input.contains("don't()") {
+            true => {
+                let (input, _) = input.split_once("don't()").unwrap();
+                compute(input)
+            },
+            false => compute(&input),
+        };
+
+        Ok(Solution::from(p2))
+    }
 }
 
 fn compute(input: &str) -> u64 {
     let matches = regex::Regex::new(r"mul\(\d+,\d+\)").unwrap();
 
-    // iter matches, split on comma, parse, multiply, sum
+    // iter matches, parse both numbers, multiply, sum
     matches
         .find_iter(input)
-        .map(|m| {
-            let mut nums = m.as_str().split(['(', ',', ')']);
-            let a = nums.nth(1).unwrap().parse::<u64>().unwrap();
-            let b = nums.next().unwrap().parse::<u64>().unwrap();
-            a * b
-        })
+        .map(|m| m.as_str().iter_unsigned::<u64>().product::<u64>())
         .sum()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::etc::solution::Solution;
+    use super::*;
 
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day03/test.txt");
-        let (part1, part2) = super::solve(input);
+        let (part1, part2) = Day03::solve(input).unwrap();
         assert_eq!(part1, Solution::U64(161));
         assert_eq!(part2, Solution::U64(48));
     }
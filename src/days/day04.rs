@@ -1,66 +1,48 @@
+use crate::utils::grid::Grid;
 use crate::utils::vector_2d::*;
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 ///////////////////////////////////////////////////////////////////////////////
 
-struct Grid(Vec<Vec<char>>);
+pub struct Day04;
 
-impl Grid {
-    fn new(input: &str) -> Self {
-        Self(
-            input
-                .lines()
-                .map(|line| line.chars().collect::<Vec<_>>())
-                .collect::<Vec<_>>(),
-        )
-    }
+impl Solver for Day04 {
+    type Input<'a> = Grid<u8>;
 
-    fn get(&self, pos: Vector2) -> Option<char> {
-        self.0
-            .get(pos.y as usize)
-            .and_then(|row| row.get(pos.x as usize).copied())
+    fn parse(input: &str) -> Result<Grid<u8>, Error> {
+        Grid::parse(input, YAxis::Up)
     }
 
-    fn iter(&self) -> impl Iterator<Item = &Vec<char>> {
-        self.0.iter()
+    fn part1(grid: &Grid<u8>) -> Result<Solution, Error> {
+        Ok(Solution::from(p1(grid)))
     }
-}
-
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = Grid::new(input);
-
-    let p1 = p1(&grid);
-    let p2 = p2(&grid);
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(grid: &Grid<u8>) -> Result<Solution, Error> {
+        Ok(Solution::from(p2(grid)))
+    }
 }
 
-fn p1(grid: &Grid) -> u32 {
+fn p1(grid: &Grid<u8>) -> u32 {
     let mut sum = 0;
-    for (y, column) in grid.iter().enumerate() {
-        for (x, letter) in column.iter().enumerate() {
-            if letter == &'X' {
-                let p = Vector2::new_usize(x, y);
-                for dir in DIRS {
-                    sum += check_dir(p, dir, grid);
-                }
-            }
+    for p in grid.positions_of(&b'X') {
+        for dir in DIRS {
+            sum += check_dir(p, dir, grid);
         }
     }
 
     sum
 }
 
-fn check_dir(p: Vector2, dir: Vector2, grid: &Grid) -> u32 {
+fn check_dir(p: Vector2, dir: Vector2, grid: &Grid<u8>) -> u32 {
     let mut buffer = [None; 4];
     for (i, element) in buffer.iter_mut().enumerate() {
-        let offset = dir * i;
-        *element = grid.get(p + offset);
+        let offset = dir * i as isize;
+        *element = grid.get(p + offset).copied();
     }
 
-    if buffer[0] == Some('X')
-        && buffer[1] == Some('M')
-        && buffer[2] == Some('A')
-        && buffer[3] == Some('S')
+    if buffer[0] == Some(b'X')
+        && buffer[1] == Some(b'M')
+        && buffer[2] == Some(b'A')
+        && buffer[3] == Some(b'S')
     {
         return 1;
     }
@@ -68,25 +50,16 @@ fn check_dir(p: Vector2, dir: Vector2, grid: &Grid) -> u32 {
     0
 }
 
-fn p2(grid: &Grid) -> u32 {
+fn p2(grid: &Grid<u8>) -> u32 {
     let mut sum = 0;
-    for (y, column) in grid.iter().enumerate() {
-        for (x, letter) in column.iter().enumerate() {
-            if letter == &'A' {
-                let p = Vector2::new_usize(x, y);
-                let mut corners = [None; 4];
-                for i in 0..4 {
-                    let diagonal = DIAGONALS[i];
-                    corners[i] = grid.get(p + diagonal);
-                }
-
-                let only_ms = || corners.iter().all(|&c| matches!(c, Some('M') | Some('S')));
-                let check_diagonals = || corners[0] != corners[2] && corners[1] != corners[3];
-
-                if only_ms() && check_diagonals() {
-                    sum += 1;
-                }
-            }
+    for p in grid.positions_of(&b'A') {
+        let corners = DIAGONALS.map(|diagonal| grid.get(p + diagonal).copied());
+
+        let only_ms = || corners.iter().all(|&c| matches!(c, Some(b'M') | Some(b'S')));
+        let check_diagonals = || corners[0] != corners[2] && corners[1] != corners[3];
+
+        if only_ms() && check_diagonals() {
+            sum += 1;
         }
     }
 
@@ -100,7 +73,7 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day04/test.txt");
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day04::solve(input).unwrap();
         assert_eq!(p1, Solution::U32(18));
         assert_eq!(p2, Solution::U32(9));
     }
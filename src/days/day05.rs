@@ -1,6 +1,7 @@
 use hashbrown::HashSet;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 
 type Pair = (u32, u32);
 enum Instruction {
@@ -51,43 +52,69 @@ impl Sorter {
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (sorting_input, rows) = input.split_once("\n\n").unwrap();
-    let sorting = sorting_input
-        .lines()
-        .map(|l| {
-            let (left, right) = l.split_once('|').unwrap();
-            (left.parse::<u32>().unwrap(), right.parse::<u32>().unwrap())
-        })
-        .collect::<HashSet<_>>();
-
-    let sorter = Sorter {
-        instructions: sorting,
-    };
-
-    let mut p1 = Vec::new();
-    let mut p2 = Vec::new();
-    for line in rows.lines() {
-        let nums = line
-            .split(',')
-            .map(|n| n.parse::<u32>().unwrap())
-            .collect::<Vec<_>>();
-
-        match sorter.check_line(&nums) {
-            Some(num) => {
-                p1.push(num);
-            }
-            None => {
-                let nums = sorter.sort(nums);
-                let answer = nums[nums.len() / 2];
-                p2.push(answer);
-            }
-        }
+pub struct Input {
+    sorter: Sorter,
+    updates: Vec<Vec<u32>>,
+}
+
+pub struct Day05;
+
+impl Solver for Day05 {
+    type Input<'a> = Input;
+
+    fn parse(input: &str) -> Result<Input, Error> {
+        let (sorting_input, rows) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::new("expected a blank line before the updates"))?;
+        let sorting = sorting_input
+            .lines()
+            .map(|l| {
+                let (left, right) = l
+                    .split_once('|')
+                    .ok_or_else(|| Error::on_line(input, l, "expected `a|b`"))?;
+                Ok((error::parse(input, left)?, error::parse(input, right)?))
+            })
+            .collect::<Result<HashSet<_>, Error>>()?;
+
+        let sorter = Sorter {
+            instructions: sorting,
+        };
+
+        let updates = rows
+            .lines()
+            .map(|line| {
+                line.split(',')
+                    .map(|n| error::parse(input, n))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Input { sorter, updates })
     }
-    let p1 = p1.iter().sum::<u32>();
-    let p2 = p2.iter().sum::<u32>();
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part1(input: &Input) -> Result<Solution, Error> {
+        let p1 = input
+            .updates
+            .iter()
+            .filter_map(|nums| input.sorter.check_line(nums))
+            .sum::<u32>();
+
+        Ok(Solution::from(p1))
+    }
+
+    fn part2(input: &Input) -> Result<Solution, Error> {
+        let p2 = input
+            .updates
+            .iter()
+            .filter(|nums| input.sorter.check_line(nums).is_none())
+            .map(|nums| {
+                let nums = input.sorter.sort(nums.clone());
+                nums[nums.len() / 2]
+            })
+            .sum::<u32>();
+
+        Ok(Solution::from(p2))
+    }
 }
 
 #[cfg(test)]
@@ -97,7 +124,7 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day05/test.txt");
-        let (part1, part2) = super::solve(input);
+        let (part1, part2) = Day05::solve(input).unwrap();
         assert_eq!(part1, Solution::U32(143));
         assert_eq!(part2, Solution::U32(123));
     }
@@ -1,17 +1,22 @@
 use std::collections::{HashMap, HashSet};
+use std::iter;
+
+use itertools::Itertools;
 
 use crate::{
-    utils::vector_2d::{Vector2, N, ORIGIN},
-    Solution, SolutionPair,
+    utils::cycle,
+    utils::vector_2d::{Direction, Vector2, YAxis},
+    Error, Solution, Solver,
 };
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     data: HashMap<Vector2, char>,
     start: Vector2,
 }
 
 impl Map {
-    fn parse(input: &str) -> Self {
+    fn parse(input: &str) -> Result<Self, Error> {
         let mut data = input
             .trim()
             .lines()
@@ -25,57 +30,95 @@ impl Map {
             })
             .collect::<HashMap<_, _>>();
 
+        for line in input.trim().lines() {
+            let line = line.trim();
+            if let Some((i, c)) = line
+                .char_indices()
+                .find(|(_, c)| !matches!(c, '.' | '#' | '^'))
+            {
+                return Err(Error::at(
+                    input,
+                    &line[i..],
+                    format!("unexpected `{}` on the map", c),
+                ));
+            }
+        }
+
+        // a missing guard has nothing to point at, so report where the map ends
+        let end = &input[input.trim_end().len()..];
         let start = data
             .iter()
             .find_map(|(k, v)| if *v == '^' { Some(*k) } else { None })
-            .unwrap();
+            .ok_or_else(|| Error::at(input, end, "no guard `^` on the map"))?;
 
         data.entry(start).and_modify(|c| *c = '.');
 
-        Self { data, start }
+        Ok(Self { data, start })
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let mut map = Map::parse(input);
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input<'a> = Map;
+
+    fn parse(input: &str) -> Result<Map, Error> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Map) -> Result<Solution, Error> {
+        Ok(Solution::from(patrol(map)?.len()))
+    }
 
-    let p1 = sim(&map).unwrap();
+    fn part2(map: &Map) -> Result<Solution, Error> {
+        let path = patrol(map)?;
+        let mut map = map.clone();
 
-    let mut p2: usize = 0;
-    for &p in &p1 {
-        map.data.entry(p).and_modify(|c| *c = '#');
+        let mut p2: usize = 0;
+        for &p in &path {
+            map.data.entry(p).and_modify(|c| *c = '#');
 
-        if sim(&map).is_none() {
-            p2 += 1;
+            if loops(&map) {
+                p2 += 1;
+            }
+
+            map.data.entry(p).and_modify(|c| *c = '.');
         }
 
-        map.data.entry(p).and_modify(|c| *c = '.');
+        Ok(Solution::from(p2))
     }
-
-    (Solution::from(p1.len()), Solution::from(p2))
 }
 
-// here we let guard go, see if we get out or not
-fn sim(map: &Map) -> Option<Vec<Vector2>> {
-    let mut positions: HashMap<Vector2, HashSet<Vector2>> = HashMap::new();
-    positions.entry(map.start).or_default().insert(N);
-
-    let mut current = map.start;
-    let mut dir = N;
-    while let Some(&c) = map.data.get(&(current + dir)) {
-        match c {
-            '#' => dir = ORIGIN.right(dir),
-            '.' => {
-                current += dir;
-                if !positions.entry(current).or_default().insert(dir) {
-                    return None;
-                }
-            }
-            _ => unreachable!(),
+type Guard = (Vector2, Direction);
+
+// the guard's own path, which has to leave the map for either part to have an answer
+fn patrol(map: &Map) -> Result<Vec<Vector2>, Error> {
+    let mut seen = HashSet::new();
+    for guard in iter::successors(Some((map.start, Direction::N)), |guard| step(map, guard)) {
+        if !seen.insert(guard) {
+            return Err(Error::new("the guard never leaves the map"));
         }
     }
 
-    Some(positions.keys().copied().collect::<Vec<_>>())
+    Ok(seen
+        .into_iter()
+        .map(|(position, _)| position)
+        .unique()
+        .collect())
+}
+
+// here we let guard go, see if we get out or not
+fn loops(map: &Map) -> bool {
+    cycle::has_cycle((map.start, Direction::N), |guard| step(map, guard))
+}
+
+// one move of the guard, or `None` once they walk off the map
+fn step(map: &Map, &(current, dir): &Guard) -> Option<Guard> {
+    let next = current + dir.vector(YAxis::Up);
+    match map.data.get(&next)? {
+        '#' => Some((current, dir.turn_right())),
+        _ => Some((next, dir)),
+    }
 }
 
 #[cfg(test)]
@@ -85,9 +128,27 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day06/test.txt");
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day06::solve(input).unwrap();
 
         assert_eq!(p1, Solution::Usize(41));
         assert_eq!(p2, Solution::Usize(6));
     }
+
+    #[test]
+    fn test_guard_stuck_in_a_loop() {
+        let map = Map::parse(".#...\n....#\n#^...\n...#.\n").unwrap();
+        let error = Day06::part1(&map).unwrap_err();
+        assert_eq!(error.to_string(), "the guard never leaves the map");
+
+        let error = Map::parse("...\n..x\n.^.\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: unexpected `x` on the map"
+        );
+        let error = Map::parse("...\n.#.\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: no guard `^` on the map"
+        );
+    }
 }
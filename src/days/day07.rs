@@ -1,27 +1,42 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::utils::integer::Integer;
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let equations = input
-        .lines()
-        .map(|l| {
-            let (target, values) = l.split_once(": ").unwrap();
-            let values = values
-                .split(' ')
-                .map(|v| v.parse::<u64>().unwrap())
-                .collect::<Vec<_>>();
-            (target.parse::<u64>().unwrap(), values)
-        })
-        .collect::<Vec<_>>();
+type Equation = (u64, Vec<u64>);
 
-    let p1 = check(&equations, false);
-    let p2 = check(&equations, true);
+pub struct Day07;
 
-    (Solution::U64(p1), Solution::U64(p2))
+impl Solver for Day07 {
+    type Input<'a> = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Vec<Equation>, Error> {
+        input
+            .lines()
+            .map(|l| {
+                let (target, values) = l
+                    .split_once(": ")
+                    .ok_or_else(|| Error::on_line(input, l, "expected `target: values`"))?;
+                let values = values
+                    .split(' ')
+                    .map(|v| error::parse(input, v))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((error::parse(input, target)?, values))
+            })
+            .collect()
+    }
+
+    fn part1(equations: &Vec<Equation>) -> Result<Solution, Error> {
+        Ok(Solution::U64(check(equations, false)))
+    }
+
+    fn part2(equations: &Vec<Equation>) -> Result<Solution, Error> {
+        Ok(Solution::U64(check(equations, true)))
+    }
 }
 
-fn check(input: &[(u64, Vec<u64>)], allow_concat: bool) -> u64 {
+fn check(input: &[Equation], allow_concat: bool) -> u64 {
     input
         .iter()
         .filter(|(target, values)| check_line(*target, values[0], &values[1..], allow_concat))
@@ -33,18 +48,14 @@ fn check_line(target: u64, current: u64, rest: &[u64], allow_concat: bool) -> bo
     if current > target {
         return false;
     }
-    return match rest {
+    match rest {
         [] if current == target => true,
         [] => false,
         [next, rest @ ..] => {
-            (allow_concat && check_line(target, concat(current, *next), rest, allow_concat))
+            (allow_concat && check_line(target, current.concat(*next), rest, allow_concat))
             || check_line(target, current * next, rest, allow_concat)
             || check_line(target, current + next, rest, allow_concat)
         }
-    };
-    
-    fn concat(current: u64, next: u64) -> u64 {
-        current * 10u64.pow(next.ilog10() + 1) + next
     }
 }
 
@@ -55,7 +66,7 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day07/test.txt");
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day07::solve(input).unwrap();
         assert_eq!(p1, Solution::U64(3749));
         assert_eq!(p2, Solution::U64(11387));
     }
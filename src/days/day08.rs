@@ -2,18 +2,21 @@ use hashbrown::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::{utils::vector_2d::Vector2, Solution, SolutionPair};
+use crate::{
+    utils::vector_2d::{Rect, Vector2},
+    Error, Solution, Solver,
+};
 
-struct Antennas {
+pub struct Antennas {
     antennas: HashMap<char, Vec<Vector2>>,
-    boundary: Vector2,
+    boundary: Rect,
 }
 
 impl Antennas {
     fn parse(input: &str) -> Self {
         let mut antennas = Antennas {
             antennas: HashMap::new(),
-            boundary: Vector2::default(),
+            boundary: Rect::default(),
         };
 
         for (y, line) in input.lines().rev().enumerate() {
@@ -21,7 +24,7 @@ impl Antennas {
                 if c != '.' {
                     antennas.add(c, Vector2::new_usize(x, y));
                 }
-                antennas.boundary = antennas.boundary.max(Vector2::new_usize(x, y));
+                antennas.boundary = antennas.boundary.including(Vector2::new_usize(x, y));
             }
         }
 
@@ -31,24 +34,27 @@ impl Antennas {
     fn add(&mut self, c: char, v: Vector2) {
         self.antennas.entry(c).or_default().push(v);
     }
-
-    fn is_inside(&self, antinode: Vector2) -> bool {
-        0 <= antinode.x
-            && 0 <= antinode.y
-            && antinode.x <= self.boundary.x
-            && antinode.y <= self.boundary.y
-    }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let antennas = Antennas::parse(input);
+pub struct Day08;
 
-    let p1 = p1(&antennas);
-    let p2 = p2(&antennas);
+impl Solver for Day08 {
+    type Input<'a> = Antennas;
+
+    fn parse(input: &str) -> Result<Antennas, Error> {
+        Ok(Antennas::parse(input))
+    }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part1(antennas: &Antennas) -> Result<Solution, Error> {
+        Ok(Solution::from(p1(antennas)))
+    }
+
+    fn part2(antennas: &Antennas) -> Result<Solution, Error> {
+        Ok(Solution::from(p2(antennas)))
+    }
 }
 
+#[allow(deprecated)] // itertools 0.15 deprecates tuple_combinations
 fn p1(input: &Antennas) -> usize {
     let mut antinodes = HashSet::new();
     for (&_c, antennas) in &input.antennas {
@@ -59,7 +65,7 @@ fn p1(input: &Antennas) -> usize {
 
             let candidates = [a - distance, b + distance];
             for &antinode in &candidates {
-                if input.is_inside(antinode) {
+                if input.boundary.contains(antinode) {
                     // println!("antinode: {:?}", antinode);
                     antinodes.insert(antinode);
                 }
@@ -69,6 +75,7 @@ fn p1(input: &Antennas) -> usize {
     antinodes.len()
 }
 
+#[allow(deprecated)] // itertools 0.15 deprecates tuple_combinations
 fn p2(input: &Antennas) -> usize {
     let mut antinodes = HashSet::new();
     for (&_c, antennas) in &input.antennas {
@@ -80,7 +87,7 @@ fn p2(input: &Antennas) -> usize {
             let mut i = 0;
             loop {
                 let candidate = a + (distance * i);
-                if !input.is_inside(candidate) {
+                if !input.boundary.contains(candidate) {
                     break;
                 }
                 antinodes.insert(candidate);
@@ -90,7 +97,7 @@ fn p2(input: &Antennas) -> usize {
             let mut i = 0;
             loop {
                 let candidate = b - (distance * i);
-                if !input.is_inside(candidate) {
+                if !input.boundary.contains(candidate) {
                     break;
                 }
                 antinodes.insert(candidate);
@@ -108,7 +115,7 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day08/test.txt");
-        let (p1, p2) = solve(input);
+        let (p1, p2) = Day08::solve(input).unwrap();
 
         assert_eq!(p1, Solution::Usize(14));
         assert_eq!(p2, Solution::Usize(34));
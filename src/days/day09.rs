@@ -1,7 +1,6 @@
-#![allow(dead_code)]
 use std::collections::VecDeque;
 
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 // use itertools;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,7 +10,9 @@ struct Count(u32);
 
 struct HardDrive {
     files: VecDeque<(Id, Count)>,
+    #[allow(dead_code)] // only read by the unused block-by-block helpers
     front_index: usize,
+    #[allow(dead_code)] // only read by the unused block-by-block helpers
     back_index: usize,
 }
 
@@ -151,15 +152,30 @@ impl HardDrive {
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let digits = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect::<Vec<_>>();
+pub struct Day09;
 
-    let (p1, p2) = (p1(&digits), p2(&digits));
+impl Solver for Day09 {
+    type Input<'a> = Vec<u32>;
 
-    (Solution::from(p1), Solution::from(p2))
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        let disk_map = input.trim_end();
+        disk_map
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10).ok_or_else(|| {
+                    Error::at(input, &disk_map[i..], format!("expected a digit, found `{c}`"))
+                })
+            })
+            .collect()
+    }
+
+    fn part1(digits: &Vec<u32>) -> Result<Solution, Error> {
+        Ok(Solution::from(p1(digits)))
+    }
+
+    fn part2(digits: &Vec<u32>) -> Result<Solution, Error> {
+        Ok(Solution::from(p2(digits)))
+    }
 }
 
 fn p1(input: &[u32]) -> u32 {
@@ -180,7 +196,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day09/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day09::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(1928));
         assert_eq!(p2, Solution::U32(2858));
     }
@@ -1,44 +1,54 @@
 use std::collections::HashSet;
 
-use crate::{Solution, SolutionPair};
-
-pub fn solve(input: &str) -> SolutionPair {
-    let map = input
-        .lines()
-        .rev()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<_>>();
-
-    let (mut p1, mut p2) = (0, 0_usize);
-
-    for y in 0..map.len() {
-        for x in 0..map[0].len() {
-            if map[y][x] == b'0' {
-                let trailheads = traverse(&map, (x, y));
-                p1 += trailheads.iter().collect::<HashSet<_>>().len();
-                p2 += trailheads.len();
-            }
-        }
+use crate::utils::grid::Grid;
+use crate::utils::vector_2d::{Vector2, YAxis};
+use crate::{Error, Solution, Solver};
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    /// The peak at the end of every trail, for each trailhead.
+    type Input<'a> = Vec<Vec<Vector2>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<Vector2>>, Error> {
+        let map = Grid::parse(input, YAxis::Up)?;
+        Ok(map
+            .positions_of(&b'0')
+            .map(|start| traverse(&map, start))
+            .collect())
     }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part1(trailheads: &Vec<Vec<Vector2>>) -> Result<Solution, Error> {
+        let p1 = trailheads
+            .iter()
+            .map(|trails| trails.iter().collect::<HashSet<_>>().len())
+            .sum::<usize>();
+
+        Ok(Solution::from(p1))
+    }
+
+    fn part2(trailheads: &Vec<Vec<Vector2>>) -> Result<Solution, Error> {
+        let p2 = trailheads.iter().map(|trails| trails.len()).sum::<usize>();
+
+        Ok(Solution::from(p2))
+    }
 }
 
-fn traverse(map: &[&[u8]], start: (usize, usize)) -> Vec<(usize, usize)> {
+fn traverse(map: &Grid<u8>, start: Vector2) -> Vec<Vector2> {
     let mut stack = vec![start];
     let mut seen = Vec::new();
-    while let Some((x, y)) = stack.pop() {
-        if map[y][x] == b'9' {
-            seen.push((x, y));
+    while let Some(p) = stack.pop() {
+        if map[p] == b'9' {
+            seen.push(p);
             continue;
         }
 
-        let current_height = map[y][x];
+        let current_height = map[p];
 
-        for (x2, y2) in [(x, y+1), (x, y-1), (x+1, y), (x-1, y)] {
-            if let Some(tile) = map.get(y2).and_then(|row| row.get(x2)) {
-                if *tile - current_height == 1 {
-                    stack.push((x2, y2));
+        for next in p.adjacent_points() {
+            if let Some(tile) = map.get(next) {
+                if tile.wrapping_sub(current_height) == 1 {
+                    stack.push(next);
                 }
             }
         }
@@ -55,7 +65,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day10/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day10::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(36));
         assert_eq!(p2, Solution::Usize(81));
     }
@@ -1,22 +1,36 @@
 use hashbrown::HashMap;
 
-use crate::{Solution, SolutionPair};
-
-pub fn solve(input: &str) -> SolutionPair {
-    let mut stones = input
-        .split(' ')
-        .map(|w| (w.parse().unwrap(), 1))
-        .collect::<HashMap<_, _>>();
-
-    let mut p1 = 0;
-    for i in 0..75 {
-        if i == 25 {
-            p1 = stones.values().sum();
-        }
+use crate::etc::error;
+use crate::utils::integer::Integer;
+use crate::{Error, Solution, Solver};
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input<'a> = HashMap<u64, usize>;
+
+    fn parse(input: &str) -> Result<HashMap<u64, usize>, Error> {
+        input
+            .split_whitespace()
+            .map(|w| Ok((error::parse(input, w)?, 1)))
+            .collect()
+    }
+
+    fn part1(stones: &HashMap<u64, usize>) -> Result<Solution, Error> {
+        Ok(Solution::from(blink(stones, 25)))
+    }
+
+    fn part2(stones: &HashMap<u64, usize>) -> Result<Solution, Error> {
+        Ok(Solution::from(blink(stones, 75)))
+    }
+}
+
+fn blink(stones: &HashMap<u64, usize>, times: usize) -> usize {
+    let mut stones = stones.clone();
+    for _ in 0..times {
         stones = update(&stones);
     }
-    let p2 = stones.values().sum::<usize>();
-    (Solution::from(p1), Solution::from(p2))
+    stones.values().sum::<usize>()
 }
 
 fn update(old_state: &HashMap<u64, usize>) -> HashMap<u64, usize> {
@@ -25,9 +39,9 @@ fn update(old_state: &HashMap<u64, usize>) -> HashMap<u64, usize> {
         match value {
             0 => *state.entry(1).or_default() += count,
             _ => {
-                let digits = value.ilog10() + 1;
+                let digits = value.digit_count();
                 if digits % 2 == 0 {
-                    let (left, right) = split(value, digits);
+                    let (left, right) = value.split_digits(digits / 2);
                     *state.entry(left).or_default() += count;
                     *state.entry(right).or_default() += count;
                 } else {
@@ -39,14 +53,6 @@ fn update(old_state: &HashMap<u64, usize>) -> HashMap<u64, usize> {
     state
 }
 
-#[inline]
-fn split(value: u64, digits: u32) -> (u64, u64) {
-    let divisor = 10u64.pow(digits / 2);
-    let left = value / divisor;
-    let right = value % divisor;
-    (left, right)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,7 +61,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day11/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day11::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(55312));
         assert_eq!(p2, Solution::Usize(65601038650482));
     }
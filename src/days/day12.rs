@@ -1,22 +1,32 @@
 use hashbrown::HashSet;
 
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = input
-        .lines()
-        .rev()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<_>>();
+pub struct Day12;
 
-    let regions = regions(&grid);
-    // Your solution here...
-    let p1 = regions.iter().map(price_p1).sum::<usize>();
-    let p2 = 0_usize;
+impl Solver for Day12 {
+    type Input<'a> = Vec<HashSet<(usize, usize)>>;
 
-    (Solution::from(p1), Solution::from(p2))
+    fn parse(input: &str) -> Result<Vec<HashSet<(usize, usize)>>, Error> {
+        let grid = input
+            .lines()
+            .rev()
+            .map(|l| l.as_bytes())
+            .collect::<Vec<_>>();
+
+        Ok(regions(&grid))
+    }
+
+    fn part1(regions: &Vec<HashSet<(usize, usize)>>) -> Result<Solution, Error> {
+        Ok(Solution::from(regions.iter().map(price_p1).sum::<usize>()))
+    }
+
+    fn part2(_regions: &Vec<HashSet<(usize, usize)>>) -> Result<Solution, Error> {
+        // Your solution here...
+        Ok(Solution::from(0_usize))
+    }
 }
 
 fn regions(grid: &[&[u8]]) -> Vec<HashSet<(usize, usize)>> {
@@ -97,7 +107,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day12/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day12::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(1930));
         assert_eq!(p2, Solution::Usize(0));
     }
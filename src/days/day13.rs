@@ -1,40 +1,69 @@
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::utils::linalg;
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 const P2_MULTIPLIER: isize = 10_000_000_000_000;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let xs = input
-        .split(|c: char| !c.is_ascii_digit())
-        .filter(|w| !w.is_empty())
-        .map(|w| w.parse().unwrap())
-        .tuples();
+type Machine = (isize, isize, isize, isize, isize, isize);
 
-    let (mut p1, mut p2) = (0, 0);
-    for (ax, ay, bx, by, tx, ty) in xs {
-        p1 += solve_single(ax, ay, bx, by, tx, ty);
-        p2 += solve_single(ax, ay, bx, by, tx + P2_MULTIPLIER, ty + P2_MULTIPLIER);
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input<'a> = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Vec<Machine>, Error> {
+        input
+            .split("\n\n")
+            .filter(|machine| !machine.trim().is_empty())
+            .map(|machine| {
+                let numbers = machine
+                    .split(|c: char| !c.is_ascii_digit())
+                    .filter(|w| !w.is_empty())
+                    .map(|w| error::parse(input, w))
+                    .collect::<Result<Vec<_>, _>>()?;
+                numbers.into_iter().collect_tuple().ok_or_else(|| {
+                    Error::on_line(input, machine, "expected two buttons and a prize")
+                })
+            })
+            .collect()
     }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part1(machines: &Vec<Machine>) -> Result<Solution, Error> {
+        let p1 = machines
+            .iter()
+            .map(|&(ax, ay, bx, by, tx, ty)| solve_single(ax, ay, bx, by, tx, ty))
+            .sum::<isize>();
+
+        Ok(Solution::from(p1))
+    }
+
+    fn part2(machines: &Vec<Machine>) -> Result<Solution, Error> {
+        let p2 = machines
+            .iter()
+            .map(|&(ax, ay, bx, by, tx, ty)| {
+                solve_single(ax, ay, bx, by, tx + P2_MULTIPLIER, ty + P2_MULTIPLIER)
+            })
+            .sum::<isize>();
+
+        Ok(Solution::from(p2))
+    }
 }
 
 // Button A: X+94, Y+34
 // Button B: X+22, Y+67
 // Prize: X=8400, Y=5400
 fn solve_single(ax: isize, ay: isize, bx: isize, by: isize, tx: isize, ty: isize) -> isize {
-    let b = (ty * ax - tx * ay) / (by * ax - bx * ay);
-    let a = (tx - b * bx) / ax;
-    
-    let x = ax * a + bx * b;
-    let y = ay * a + by * b;
+    let buttons = [vec![ax as i128, bx as i128], vec![ay as i128, by as i128]];
+    let prize = [tx as i128, ty as i128];
 
-    if (x, y) != (tx, ty) {
-        return 0;
+    // A costs 3 tokens and B costs 1, unreachable prizes cost nothing
+    match linalg::cheapest_natural(&buttons, &prize, &[3, 1]) {
+        Some((tokens, _)) => tokens as isize,
+        None => 0,
     }
-    a * 3 + b
 }
 
 #[cfg(test)]
@@ -49,12 +78,31 @@ mod tests {
         println!("{}", rem)
     }
 
+    #[test]
+    fn test_collinear() {
+        // B moves a third as far as A for a third of the cost, so either way costs the same
+        assert_eq!(solve_single(3, 6, 1, 2, 10, 20), 10);
+        // both buttons move an even distance and the prize is an odd distance away
+        assert_eq!(solve_single(2, 4, 4, 8, 5, 10), 0);
+    }
+
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day13/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day13::solve(input).unwrap();
         assert_eq!(p1, Solution::Isize(480));
         assert_eq!(p2, Solution::Isize(875318608908));
     }
+
+    #[test]
+    fn test_incomplete_machine() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                     Button A: X+26, Y+66\nButton B: X+67, Y+21\n";
+        let error = Day13::parse(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5: expected two buttons and a prize"
+        );
+    }
 }
@@ -1,86 +1,70 @@
-#![allow(dead_code)]
 use itertools::Itertools;
 
-use crate::utils::iter::*;
 use crate::utils::parse::*;
+use crate::utils::vector_2d::{Rect, Vector2};
 
-use std::cmp::Ordering::*;
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
-type Robot = [usize; 4];
+/// The position and velocity of a robot.
+type Robot = (Vector2, Vector2);
 
-pub fn solve(input: &str) -> SolutionPair {
-    solve_inner::<101, 103>(input)
-}
-
-fn solve_inner<const WIDTH: usize, const HEIGHT: usize>(input: &str) -> SolutionPair {
-    let robots = input
-        .iter_signed::<i32>()
-        .chunk::<4>()
-        .map(|[x, y, dx, dy]| {
-            [x as usize, y as usize, dx.rem_euclid(WIDTH as i32) as usize, dy.rem_euclid(HEIGHT as i32) as usize]
-        })
-        .collect::<Vec<_>>();
+pub struct Day14<const WIDTH: usize = 101, const HEIGHT: usize = 103>;
 
-    let p1 = p1::<WIDTH, HEIGHT>(&robots, 100);
-    let p2 = match p2::<WIDTH, HEIGHT>(robots) {
-        Some(p2) => Solution::Usize(p2),
-        None => Solution::None,
-    };
+impl<const WIDTH: usize, const HEIGHT: usize> Solver for Day14<WIDTH, HEIGHT> {
+    type Input<'a> = Vec<Robot>;
 
-    (Solution::from(p1), p2)
-}
-
-fn p1<const WIDTH: usize, const HEIGHT: usize>(robots: &[Robot], seconds: usize) -> usize {
-    let half_width = WIDTH / 2;
-    let half_height = HEIGHT / 2;
-
-    let mut quadrants = [0; 4];
+    fn parse(input: &str) -> Result<Vec<Robot>, Error> {
+        input
+            .lines()
+            .map(|line| {
+                let (x, y, dx, dy) = line
+                    .iter_signed::<isize>()
+                    .collect_tuple()
+                    .ok_or_else(|| Error::on_line(input, line, "expected `p=x,y v=dx,dy`"))?;
+                Ok((Vector2::new(x, y), Vector2::new(dx, dy)))
+            })
+            .collect()
+    }
 
-    for [x, y, dx, dy] in robots {
-        let x = (x + dx * seconds) % WIDTH;
-        let y = (y + dy * seconds) % HEIGHT;
+    fn part1(robots: &Vec<Robot>) -> Result<Solution, Error> {
+        Ok(Solution::from(p1::<WIDTH, HEIGHT>(robots, 100)))
+    }
 
-        match (x.cmp(&half_width), y.cmp(&half_height)) {
-            (Less, Less) => quadrants[0] += 1,
-            (Less, Greater) => quadrants[1] += 1,
-            (Greater, Less) => quadrants[2] += 1,
-            (Greater, Greater) => quadrants[3] += 1,
-            _ => (),
+    fn part2(robots: &Vec<Robot>) -> Result<Solution, Error> {
+        match p2::<WIDTH, HEIGHT>(robots.clone()) {
+            Some(p2) => Ok(Solution::Usize(p2)),
+            None => Err(Error::new("the robots never all stand on different tiles")),
         }
     }
+}
+
+fn p1<const WIDTH: usize, const HEIGHT: usize>(robots: &[Robot], seconds: usize) -> usize {
+    let area = Rect::from_size(WIDTH, HEIGHT);
+    let positions = robots
+        .iter()
+        .map(|&(p, v)| area.wrap(p + v * seconds as isize))
+        .collect::<Vec<_>>();
 
-    quadrants.iter().product()
+    area.quadrants()
+        .iter()
+        .map(|quadrant| positions.iter().filter(|&&p| quadrant.contains(p)).count())
+        .product()
 }
 
 fn p2<const WIDTH: usize, const HEIGHT: usize>(mut robots: Vec<Robot>) -> Option<usize> {
-    for i in 1.. {
-        for [x, y, dx, dy] in robots.iter_mut() {
-            *x = (*x + *dx).rem_euclid(WIDTH);
-            *y = (*y + *dy).rem_euclid(HEIGHT);
+    // every robot is back where it started after WIDTH * HEIGHT seconds, so the robots repeat
+    // from the first second with a known period and there is no cycle for `utils::cycle` to find
+    let area = Rect::from_size(WIDTH, HEIGHT);
+    for i in 1..=WIDTH * HEIGHT {
+        for (p, v) in robots.iter_mut() {
+            *p = area.wrap(*p + *v);
         }
-        if robots.iter().map(|&[x, y, _, _]| (x, y)).all_unique() {
-            // print_grid::<WIDTH, HEIGHT>(&robots);
-
+        if robots.iter().map(|&(p, _)| p).all_unique() {
             return Some(i);
         }
-        // pattern should have been found within WIDTH * HEIGHT iterations
-        if i > WIDTH * HEIGHT { 
-            return None;
-        }
     }
 
-    unreachable!()
-}
-
-fn print_grid<const WIDTH: usize, const HEIGHT: usize>(robots: &[Robot]) {
-    let mut grid = [['.'; WIDTH]; HEIGHT];
-    for [x, y, _, _] in robots.iter() {
-        grid[*y][*x] = '#';
-    }
-    for row in grid.iter() {
-        println!("{}", row.iter().join(""));
-    }
+    None
 }
 
 #[cfg(test)]
@@ -91,8 +75,14 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day14/test.txt");
 
-        let (p1, p2) = super::solve_inner::<11, 7>(input);
+        let (p1, p2) = Day14::<11, 7>::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(12));
         assert_eq!(p2, Solution::Usize(1));
     }
+
+    #[test]
+    fn test_incomplete_robot() {
+        let error = Day14::<11, 7>::parse("p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected `p=x,y v=dx,dy`");
+    }
 }
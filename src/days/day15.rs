@@ -1,4 +1,6 @@
-use crate::{Solution, SolutionPair};
+use crate::utils::grid::Grid;
+use crate::utils::vector_2d::{Direction, Vector2, YAxis};
+use crate::{Error, Solution, Solver};
 
 const BOX: u8 = b'O';
 const _WIDE_BOX_LEFT: u8 = b'[';
@@ -8,84 +10,47 @@ const WALL: u8 = b'#';
 const ROBOT: u8 = b'@';
 
 #[derive(Debug, Clone)]
-struct Grid {
-    grid: Vec<Vec<u8>>,
-    robot: (usize, usize),
+struct Warehouse {
+    grid: Grid<u8>,
+    robot: Vector2,
 }
-impl Grid {
-    fn new(grid: Vec<Vec<u8>>) -> Self {
+impl Warehouse {
+    fn new(grid: Grid<u8>) -> Result<Self, Error> {
         let robot = grid
-            .iter()
-            .enumerate()
-            .find_map(|(y, row)| {
-                row.iter().enumerate().find_map(
-                    |(x, &v)| {
-                        if v == ROBOT {
-                            Some((x, y))
-                        } else {
-                            None
-                        }
-                    },
-                )
-            })
-            .unwrap();
-
-        Self { grid, robot }
-    }
-
-    fn _print(&self) {
-        for row in &self.grid {
-            for &v in row {
-                print!("{}", v as char);
-            }
-            println!();
-        }
-        println!();
-    }
-
-    fn get(&self, x: usize, y: usize) -> Option<&u8> {
-        self.grid.get(y).and_then(|row| row.get(x))
-    }
+            .find(|&v| v == ROBOT)
+            .ok_or_else(|| Error::new("no robot `@` in the warehouse"))?;
 
-    fn set(&mut self, x: usize, y: usize, v: u8) {
-        self.grid[y][x] = v;
+        Ok(Self { grid, robot })
     }
 
     fn move_robot(&mut self, instruction: u8) {
-        let (x, y) = self.robot;
-        let (x2, y2) = match instruction {
-            b'^' => (x, y - 1),
-            b'v' => (x, y + 1),
-            b'>' => (x + 1, y),
-            b'<' => (x - 1, y),
-            _ => return,
+        let Some(dir) = Direction::from_byte(instruction) else {
+            return;
         };
+        let dir = dir.vector(self.grid.axis);
+        let next = self.robot + dir;
 
-        match self.get(x2, y2) {
+        match self.grid.get(next) {
             Some(first) => {
                 match *first {
                     EMPTY => {
                         // swap robot and empty
-                        self.set(x, y, EMPTY);
-                        self.set(x2, y2, ROBOT);
-                        self.robot = (x2, y2);
+                        self.grid[self.robot] = EMPTY;
+                        self.grid[next] = ROBOT;
+                        self.robot = next;
                     }
                     BOX => {
-                        let dir = (x2 as isize - x as isize, y2 as isize - y as isize);
-
-                        let mut next = (x2 + dir.0 as usize, y2 + dir.1 as usize);
-                        while let Some(last) =
-                            self.get(next.0, next.1)
-                        {
-                            match *last {
+                        let mut last = next + dir;
+                        while let Some(tile) = self.grid.get(last) {
+                            match *tile {
                                 BOX => {
-                                    next = (next.0 + dir.0 as usize, next.1 + dir.1 as usize);
+                                    last += dir;
                                 },
                                 EMPTY => {
-                                    self.set(x, y, EMPTY);
-                                    self.set(x2, y2, ROBOT);
-                                    self.set(next.0, next.1, BOX);
-                                    self.robot = (x2, y2);
+                                    self.grid[self.robot] = EMPTY;
+                                    self.grid[next] = ROBOT;
+                                    self.grid[last] = BOX;
+                                    self.robot = next;
                                     break;
                                 }
                                 WALL => break,
@@ -102,43 +67,52 @@ impl Grid {
     }
 
     fn sum_gps(&self) -> usize {
-        // find all O
         // value is 100* distance to top wall + distance to left wall
-
-        self.grid.iter().enumerate().fold(0, |acc, (y, row)| {
-            acc + row.iter().enumerate().fold(
-                0,
-                |acc, (x, &v)| {
-                    if v == BOX {
-                        acc + 100 * y + x
-                    } else {
-                        acc
-                    }
-                },
-            )
-        })
+        self.grid
+            .positions_of(&BOX)
+            .map(|pos| {
+                let (x, y) = self.grid.screen_position(pos);
+                100 * y + x
+            })
+            .sum()
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (map, instructions) = input.split_once("\n\n").unwrap();
-    let grid = map
-        .lines()
-        .map(|l| l.as_bytes().to_vec())
-        .collect::<Vec<_>>();
+pub struct Input<'a> {
+    warehouse: Warehouse,
+    instructions: &'a str,
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Input<'a> = Input<'a>;
 
-    let mut grid = Grid::new(grid);
-    // grid.print();
+    fn parse(input: &str) -> Result<Input<'_>, Error> {
+        let (map, instructions) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::new("expected a blank line before the moves"))?;
+        let grid = Grid::parse(map, YAxis::Down)?;
 
-    for instruction in instructions.trim().bytes() {
-        grid.move_robot(instruction);
-        // grid.print();
+        Ok(Input {
+            warehouse: Warehouse::new(grid)?,
+            instructions: instructions.trim(),
+        })
     }
 
-    let p1 = grid.sum_gps();
-    let p2 = 0_usize;
+    fn part1(input: &Input<'_>) -> Result<Solution, Error> {
+        let mut warehouse = input.warehouse.clone();
+
+        for instruction in input.instructions.bytes() {
+            warehouse.move_robot(instruction);
+        }
+
+        Ok(Solution::from(warehouse.sum_gps()))
+    }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(_input: &Input<'_>) -> Result<Solution, Error> {
+        Ok(Solution::from(0_usize))
+    }
 }
 
 #[cfg(test)]
@@ -149,7 +123,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day15/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day15::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(10092));
         assert_eq!(p2, Solution::Usize(0));
     }
@@ -158,7 +132,7 @@ mod tests {
     fn test_small_input() {
         let input = include_str!("../../input/day15/test_small.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day15::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(2028));
         assert_eq!(p2, Solution::Usize(0));
     }
@@ -1,125 +1,94 @@
-use std::collections::{BinaryHeap, VecDeque};
-
-use hashbrown::{HashMap, HashSet};
+use hashbrown::HashSet;
 
 use crate::{
-    utils::vector_2d::{self, Vector2},
-    Solution, SolutionPair,
+    utils::grid::Grid,
+    utils::search::{self, Space},
+    utils::vector_2d::{Direction, Vector2, YAxis},
+    Error, Solution, Solver,
 };
 
 #[derive(Debug, Clone)]
-struct Grid {
-    grid: Vec<Vec<u8>>,
+pub struct Maze {
+    grid: Grid<u8>,
     start: Vector2,
     exit: Vector2,
 }
 
-impl Grid {
-    fn new(input: &str) -> Self {
-        let grid = input
-            .lines()
-            .rev()
-            .map(|l| l.as_bytes().to_vec())
-            .collect::<Vec<_>>();
-
-        let start = Vector2::new_usize(1, 1);
-        let exit = Vector2::new_usize(grid.len() - 2, grid[0].len() - 2);
-
-        Self { grid, start, exit }
+impl Maze {
+    fn new(input: &str) -> Result<Self, Error> {
+        let grid = Grid::parse(input, YAxis::Up)?;
+        let find = |tile, name| {
+            grid.find(|&t| t == tile)
+                .ok_or_else(|| Error::new(format!("no {} in the maze", name)))
+        };
+        let start = find(b'S', "start `S`")?;
+        let exit = find(b'E', "end `E`")?;
+
+        Ok(Self { grid, start, exit })
     }
 
     fn get(&self, p: Vector2) -> Option<&u8> {
-        let y = p.row_index();
-        let x = p.column_index();
-
-        self.grid.get(y).and_then(|row| row.get(x))
-    }
-
-    fn _print(&self) {
-        for row in self.grid.iter().rev() {
-            for &v in row {
-                print!("{}", v as char);
-            }
-            println!();
-        }
-        println!();
+        self.grid.get(p)
     }
 }
 
-const TURNCOST: isize = 1000;
+const TURNCOST: usize = 1000;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = Grid::new(input);
+impl Space for Maze {
+    type State = (Vector2, Direction);
 
-    // Your solution here...
-    let rotate_left = |v| match v {
-        vector_2d::N => vector_2d::W,
-        vector_2d::E => vector_2d::N,
-        vector_2d::S => vector_2d::E,
-        vector_2d::W => vector_2d::S,
-        _ => unreachable!(),
-    };
+    fn start(&self) -> Self::State {
+        (self.start, Direction::E)
+    }
 
-    let rotate_right = |v| match v {
-        vector_2d::N => vector_2d::E,
-        vector_2d::E => vector_2d::S,
-        vector_2d::S => vector_2d::W,
-        vector_2d::W => vector_2d::N,
-        _ => unreachable!(),
-    };
+    fn successors(
+        &self,
+        &(current, dir): &Self::State,
+    ) -> impl IntoIterator<Item = (Self::State, usize)> {
+        [(dir, 1), (dir.turn_left(), TURNCOST + 1), (dir.turn_right(), TURNCOST + 1)]
+            .into_iter()
+            .map(move |(dir, cost)| ((current + dir.vector(YAxis::Up), dir), cost))
+            .filter(|((next, _), _)| matches!(self.get(*next), Some(b'.' | b'E')))
+    }
 
-    let mut q = BinaryHeap::new();
-    let mut seen = HashMap::new();
+    fn is_goal(&self, &(current, _): &Self::State) -> bool {
+        current == self.exit
+    }
+}
 
-    let mut p1 = 0;
+pub struct Day16;
 
-    q.push((0_isize, grid.start, vector_2d::E));
+impl Solver for Day16 {
+    type Input<'a> = Maze;
 
-    while let Some((score, current, dir)) = q.pop() {
-        if let Some(b'E') = grid.get(current) {
-            p1 = score.unsigned_abs();
-            continue;
-        }
-        
-        let best = seen.get(&(current, dir)).copied().unwrap_or(isize::MAX);
-        
-        if best <= score {
-            continue;
-        }
-        
-        seen.insert((current, dir), score);
-
-        let left = current.left(dir);
-        if matches!(grid.get(left).unwrap(), b'.' | b'E') {
-            q.push((score - TURNCOST - 1, left, rotate_left(dir)));
-        }
+    fn parse(input: &str) -> Result<Maze, Error> {
+        Maze::new(input)
+    }
 
-        let right = current.right(dir);
-        if matches!(grid.get(right).unwrap(), b'.' | b'E') {
-            q.push((score - TURNCOST - 1, right, rotate_right(dir)));
-        }
+    fn part1(grid: &Maze) -> Result<Solution, Error> {
+        let p1 = search::dijkstra(grid)
+            .distance()
+            .ok_or_else(|| Error::new("no path from `S` to `E`"))?;
+        Ok(Solution::from(p1))
+    }
 
-        let forward = current + dir;
-        if matches!(grid.get(forward).unwrap(), b'.' | b'E') {
-            q.push((score - 1, forward, dir));
+    fn part2(grid: &Maze) -> Result<Solution, Error> {
+        let paths = search::all_shortest_paths(grid);
+        if paths.goals.is_empty() {
+            return Err(Error::new("no path from `S` to `E`"));
         }
-    }
 
-    // traverse from the exit, find all paths until we reach the start
-    let _nodes: HashSet<Vector2> = HashSet::new();
-    let mut q = VecDeque::new();
+        // every tile on any of the best paths, whichever way the reindeer faces on it
+        let tiles: HashSet<Vector2> = paths
+            .states()
+            .into_iter()
+            .map(|(tile, _)| tile)
+            .collect();
 
-    for dir in [vector_2d::N, vector_2d::E, vector_2d::S, vector_2d::W].iter() {
-        if seen.get(&(grid.exit, *dir)).copied().unwrap_or(isize::MAX) == p1 as isize {
-            q.push_back((grid.exit, *dir, p1));
-        }
+        Ok(Solution::from(tiles.len()))
     }
-    let p2 = 0;
-
-    (Solution::from(p1), Solution::from(p2))
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,8 +97,15 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day16/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day16::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(7036));
         assert_eq!(p2, Solution::Usize(45));
     }
+
+    #[test]
+    fn test_no_path() {
+        let maze = Day16::parse("#####\n#S#E#\n#####\n").unwrap();
+        assert!(Day16::part1(&maze).is_err());
+        assert!(Day16::part2(&maze).is_err());
+    }
 }
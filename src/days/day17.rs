@@ -1,29 +1,39 @@
-#![allow(dead_code)]
 use core::panic;
 
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Clone, Copy)]
-struct Registers {
+pub struct Registers {
     a: usize,
     b: usize,
     c: usize,
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (registers, instructions) = parse(input);
-    let mut output = vec![];
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input<'a> = (Registers, Vec<usize>);
+
+    fn parse(input: &str) -> Result<(Registers, Vec<usize>), Error> {
+        parse(input)
+    }
 
-    let _ = run(registers, &instructions, |v| output.push(v));
+    fn part1((registers, instructions): &(Registers, Vec<usize>)) -> Result<Solution, Error> {
+        let mut output = vec![];
 
-    let p1 = concat(&output);
-    let p2 = p2(&instructions);
+        let _ = run(*registers, instructions, |v| output.push(v));
 
-    (Solution::from(p1), Solution::from(p2))
+        Ok(Solution::from(concat(&output)))
+    }
+
+    fn part2((_, instructions): &(Registers, Vec<usize>)) -> Result<Solution, Error> {
+        Ok(Solution::from(p2(instructions)))
+    }
 }
 
 fn p2(instructions: &[usize]) -> usize {
@@ -50,31 +60,61 @@ fn p2(instructions: &[usize]) -> usize {
     registers.a
 }
 
-fn parse(input: &str) -> (Registers, Vec<usize>) {
-    let (registers, instructions) = input.split_once("\n\n").unwrap();
+fn parse(input: &str) -> Result<(Registers, Vec<usize>), Error> {
+    let (register_lines, program) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::new("expected a blank line before the program"))?;
 
-    let registers = registers
+    let registers = register_lines
         .lines()
         .map(|l| {
-            let (_, value) = l.split_once(": ").unwrap();
-            value.parse::<usize>().unwrap()
+            let (_, value) = l
+                .split_once(": ")
+                .ok_or_else(|| Error::on_line(input, l, "expected `Register X: value`"))?;
+            error::parse::<usize>(input, value)
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let instructions = instructions
+    let words = program
         .split(|c: char| !c.is_ascii_digit())
         .filter(|w| !w.is_empty())
-        .map(|w| w.parse::<usize>().unwrap())
         .collect::<Vec<_>>();
+    let instructions = words
+        .iter()
+        .map(|w| error::parse::<usize>(input, w))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [a, b, c] = registers[..] else {
+        return Err(Error::on_line(
+            input,
+            register_lines,
+            "expected registers A, B and C",
+        ));
+    };
+    if let Some(i) = instructions.iter().position(|&v| v > 7) {
+        let reason = format!("`{}` is not a 3-bit instruction", instructions[i]);
+        return Err(Error::at(input, words[i], reason));
+    }
+    if !instructions.len().is_multiple_of(2) {
+        let last = words[words.len() - 1];
+        return Err(Error::at(
+            input,
+            last,
+            "the last instruction has no operand",
+        ));
+    }
+    if let Some(i) = (0..instructions.len())
+        .step_by(2)
+        .find(|&i| matches!(instructions[i..i + 2], [0 | 2 | 5 | 6 | 7, 7]))
+    {
+        let reason = format!(
+            "opcode {} uses the reserved combo operand 7",
+            instructions[i]
+        );
+        return Err(Error::at(input, words[i + 1], reason));
+    }
 
-    (
-        Registers {
-            a: registers[0],
-            b: registers[1],
-            c: registers[2],
-        },
-        instructions,
-    )
+    Ok((Registers { a, b, c }, instructions))
 }
 
 fn run<F>(mut registers: Registers, instructions: &[usize], mut output: F) -> Registers
@@ -156,8 +196,30 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day17/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day17::solve(input).unwrap();
         assert_eq!(p1, Solution::Str("5,7,3,0".into()));
         assert_eq!(p2, Solution::Usize(117440));
     }
+
+    #[test]
+    fn test_invalid_program() {
+        let registers = "Register A: 1\nRegister B: 0\nRegister C: 0\n\n";
+        let error = |program: &str| parse(&format!("{}{}", registers, program)).unwrap_err();
+
+        assert_eq!(
+            error("Program: 0,3,5,7\n").to_string(),
+            "line 5, column 16: opcode 5 uses the reserved combo operand 7"
+        );
+        assert_eq!(
+            error("Program: 0,8\n").to_string(),
+            "line 5, column 12: `8` is not a 3-bit instruction"
+        );
+        assert_eq!(
+            error("Program: 0,3,5\n").to_string(),
+            "line 5, column 14: the last instruction has no operand"
+        );
+
+        let error = parse("Register A: 1\nRegister B: 0\n\nProgram: 0,3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: expected registers A, B and C");
+    }
 }
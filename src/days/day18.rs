@@ -1,11 +1,27 @@
-use std::{collections::VecDeque, vec};
+use std::vec;
 
-use hashbrown::HashSet;
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
-
-type Vector2 = (usize, usize);
+use crate::etc::error;
+use crate::utils::dsu;
+use crate::utils::search::{self, Space};
+use crate::utils::vector_2d::Vector2;
+use crate::{Error, Solution, Solver};
+
+/// The memory space is only 71 bytes across, so a `u8` holds either coordinate.
+type Byte = Vector2<u8>;
+
+/// The memory spaces of the sample and the real input: their width and height, and how many
+/// bytes have fallen by part 1.
+const SPACES: [(usize, usize); 2] = [(7, 12), (71, 1024)];
+
+/// The falling bytes, in order, and the smallest memory space they all land in.
+#[derive(Debug)]
+pub struct Memory {
+    bytes: Vec<Byte>,
+    size: usize,
+    fallen: usize,
+}
 
 #[derive(Debug, Default, Clone)]
 struct Grid {
@@ -19,7 +35,12 @@ impl Grid {
         Self { grid }
     }
 
-    fn get(&self, (x, y): Vector2) -> Option<u8> {
+    fn size(&self) -> u8 {
+        self.grid.len() as u8
+    }
+
+    fn get(&self, p: Byte) -> Option<u8> {
+        let (x, y) = p.index()?;
         self.grid.get(y).and_then(|row| row.get(x).copied())
     }
 
@@ -28,85 +49,132 @@ impl Grid {
     }
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    // Your solution here...
-    let mut grid = Grid::of_size(71);
+pub struct Day18;
 
-    let mut coords = input
-        .split(|c: char| !c.is_ascii_digit() && c != '-')
-        .filter(|w| !w.is_empty())
-        .map(|w| w.parse::<usize>().unwrap())
-        .tuples();
+impl Solver for Day18 {
+    type Input<'a> = Memory;
 
-    for _ in 0..1024 {
-        let (x, y) = coords.next().unwrap();
-        grid.set(x, y, b'#');
+    fn parse(input: &str) -> Result<Memory, Error> {
+        let bytes = input
+            .lines()
+            .map(|line| {
+                let (x, y) = line
+                    .split(',')
+                    .collect_tuple()
+                    .ok_or_else(|| Error::on_line(input, line, "expected `x,y`"))?;
+                Ok(Vector2::new(
+                    error::parse(input, x)?,
+                    error::parse(input, y)?,
+                ))
+            })
+            .collect::<Result<Vec<Byte>, Error>>()?;
+
+        // The byte farthest out decides which memory space this is.
+        let (largest, line) = bytes
+            .iter()
+            .zip(input.lines())
+            .map(|(b, line)| (b.x.max(b.y) as usize, line))
+            .max_by_key(|&(far, _)| far)
+            .unwrap_or((0, ""));
+        let (size, fallen) = SPACES
+            .into_iter()
+            .find(|&(size, _)| largest < size)
+            .ok_or_else(|| {
+                Error::on_line(input, line, "byte falls outside the 71x71 memory space")
+            })?;
+        Ok(Memory {
+            bytes,
+            size,
+            fallen,
+        })
+    }
+
+    fn part1(memory: &Memory) -> Result<Solution, Error> {
+        let path = shortest_path(&memory.bytes, memory.size, memory.fallen)?;
+        Ok(Solution::from(path))
     }
 
-    let p1 = bfs(&grid, (70, 70), (0, 0)).unwrap();
-    
-    /*
-        p2 could be massively improved by recording the shortest path from p1, and then recalculate the path only when the instructions overlap with the shortest path.
-    */
-    let mut grid = grid.clone();
-    let mut p2 = String::from("(0,0)");
-    for (x, y) in coords {
-        grid.set(x, y, b'#');
-        if dfs(&grid, (70, 70), (0, 0)).is_none() {
-            p2 = format!("{},{}", x, y);
-            break;
-        }
+    fn part2(memory: &Memory) -> Result<Solution, Error> {
+        let Vector2 { x, y } = first_blocking(&memory.bytes, memory.size)?;
+        Ok(Solution::Str(format!("{},{}", x, y)))
     }
+}
 
-    (Solution::from(p1), Solution::Str(p2))
+/// The fewest steps from one corner of a `size` by `size` memory space to the other, once the
+/// first `fallen` bytes have landed.
+fn shortest_path(coords: &[Byte], size: usize, fallen: usize) -> Result<usize, Error> {
+    let fallen = coords
+        .get(..fallen)
+        .ok_or_else(|| Error::new(format!("fewer than {} bytes fall", fallen)))?;
+    let grid = drop_bytes(fallen, size)?;
+
+    let corner = Vector2::new_usize(size - 1, size - 1).cast();
+    bfs(&grid, corner, Vector2::new(0, 0))
+        .ok_or_else(|| Error::new(format!("the first {} bytes cut off the exit", fallen.len())))
 }
 
-fn bfs(grid: &Grid, start: Vector2, end: Vector2) -> Option<usize> {
-    let mut q = VecDeque::from([(0, start)]);
-    let mut visited = HashSet::new();
-
-    while let Some((steps, p)) = q.pop_front() {
-        if p == end {
-            return Some(steps);
-        }
-
-        if !visited.insert(p) {
-            continue;
-        }
-
-        let (x, y) = p;
-        for next in [(x, y + 1), (x, y - 1), (x + 1, y), (x - 1, y)] {
-            if let Some(b'.') = grid.get(next) {
-                q.push_back((steps + 1, next));
-            }
-        }
+/// The first byte to cut the corners of a `size` by `size` memory space off from each other.
+fn first_blocking(coords: &[Byte], size: usize) -> Result<Byte, Error> {
+    check_bounds(coords, size)?;
+
+    // unblocking the bytes in reverse finds the first one that cuts the exit off
+    let cells = coords.iter().map(|c| c.cast()).collect::<Vec<_>>();
+    let (start, end) = (Vector2::new_usize(size - 1, size - 1), Vector2::new(0, 0));
+
+    let i = dsu::first_disconnecting(size, size, &cells, start, end)
+        .ok_or_else(|| Error::new("no byte cuts off the exit"))?;
+    Ok(coords[i])
+}
+
+fn check_bounds(coords: &[Byte], size: usize) -> Result<(), Error> {
+    let outside = |c: &&Byte| c.x as usize >= size || c.y as usize >= size;
+    match coords.iter().find(outside) {
+        Some(c) => Err(Error::new(format!(
+            "byte {},{} falls outside the {}x{} memory space",
+            c.x, c.y, size, size
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn drop_bytes(coords: &[Byte], size: usize) -> Result<Grid, Error> {
+    check_bounds(coords, size)?;
+
+    let mut grid = Grid::of_size(size);
+    for &Vector2 { x, y } in coords {
+        grid.set(x as usize, y as usize, b'#');
     }
+    Ok(grid)
+}
 
-    None
+/// The way from `start` to `end` around the fallen bytes.
+struct Route<'a> {
+    grid: &'a Grid,
+    start: Byte,
+    end: Byte,
 }
 
-fn dfs(grid: &Grid, start: Vector2, end: Vector2) -> Option<usize> {
-    let mut q = vec![(0, start)];
-    let mut visited = HashSet::new();
-
-    while let Some((steps, p)) = q.pop() {
-        if p == end {
-            return Some(steps);
-        }
-
-        if !visited.insert(p) {
-            continue;
-        }
-
-        let (x, y) = p;
-        for next in [(x, y + 1), (x, y - 1), (x + 1, y), (x - 1, y)] {
-            if let Some(b'.') = grid.get(next) {
-                q.push((steps + 1, next));
-            }
-        }
+impl Space for Route<'_> {
+    type State = Byte;
+
+    fn start(&self) -> Byte {
+        self.start
     }
 
-    None
+    fn successors(&self, p: &Byte) -> impl IntoIterator<Item = (Byte, usize)> {
+        let size = self.grid.size();
+        p.adjacent_within(size, size)
+            .filter(|&next| self.grid.get(next) == Some(b'.'))
+            .map(|next| (next, 1))
+    }
+
+    fn is_goal(&self, &p: &Byte) -> bool {
+        p == self.end
+    }
+}
+
+fn bfs(grid: &Grid, start: Byte, end: Byte) -> Option<usize> {
+    search::bfs(&Route { grid, start, end }).distance()
 }
 
 #[cfg(test)]
@@ -116,37 +184,30 @@ mod tests {
     #[test]
     fn test_sample_input() {
         let input = include_str!("../../input/day18/test.txt");
+        let (p1, p2) = Day18::solve(input).unwrap();
+        assert_eq!(p1, Solution::Usize(22));
+        assert_eq!(p2, Solution::Str("6,1".into()));
+
+        let coords = Day18::parse(input).unwrap().bytes;
+        assert_eq!(shortest_path(&coords, 7, 12).unwrap(), 22);
+        assert_eq!(first_blocking(&coords, 7).unwrap(), Vector2::new(6, 1));
+        assert!(first_blocking(&coords, 6).is_err());
+    }
 
-        let start = (0, 0);
-        let end = (6, 6);
-        let instructions = input
-            .lines()
-            .map(|l| {
-                l.split_once(',')
-                    .map(|(x, y)| (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()))
-                    .unwrap()
-            })
-            .collect::<Vec<_>>();
-
-        let mut grid = Grid::of_size(7);
-        for instruction in instructions.iter().take(12) {
-            grid.set(instruction.0, instruction.1, b'#');
-        }
-        let steps = super::bfs(&grid, start, end);
-
-        assert_eq!(steps, Some(22));
-
-        let mut grid = grid.clone();
-
-        let mut p2 = String::from("(0,0)");
-        for &(x, y) in instructions.iter().skip(12) {
-            grid.set(x, y, b'#');
-            if bfs(&grid, start, end).is_none() {
-                p2 = format!("{},{}", x, y);
-                break;
-            }
-        }
+    #[test]
+    fn test_odd_coordinate() {
+        let error = Day18::parse("5,4\n4\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected `x,y`");
+    }
 
-        assert_eq!(p2, "6,1");
+    #[test]
+    fn test_memory_size() {
+        let memory = Day18::parse("5,4\n70,2\n").unwrap();
+        assert_eq!((memory.size, memory.fallen), (71, 1024));
+        let error = Day18::parse("5,4\n71,2\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: byte falls outside the 71x71 memory space"
+        );
     }
 }
@@ -1,26 +1,45 @@
 #![allow(unused_mut)]
 use hashbrown::HashMap;
 
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
 type Cache<'a> = HashMap<&'a [u8], usize>;
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (towels, patterns) = input.split_once("\n\n").unwrap();
-    let towels = towels.split(", ").map(str::as_bytes).collect::<Vec<_>>();
+pub struct Input<'a> {
+    towels: Vec<&'a [u8]>,
+    patterns: Vec<&'a [u8]>,
+}
 
-    let (mut p1, mut p2) = (0, 0);
+pub struct Day19;
 
-    for pattern in patterns.lines() {
-        let mut cache = HashMap::new();
-        let paths = dfs(pattern.as_bytes(), &towels, &mut cache);
-        if paths > 0 {
-            p1 += 1;
-            p2 += paths;
-        }
+impl Solver for Day19 {
+    type Input<'a> = Input<'a>;
+
+    fn parse(input: &str) -> Result<Input<'_>, Error> {
+        let (towels, patterns) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::new("expected a blank line before the designs"))?;
+        let towels = towels.split(", ").map(str::as_bytes).collect::<Vec<_>>();
+        let patterns = patterns.lines().map(str::as_bytes).collect::<Vec<_>>();
+
+        Ok(Input { towels, patterns })
+    }
+
+    fn part1(input: &Input<'_>) -> Result<Solution, Error> {
+        let p1 = arrangements(input).filter(|&paths| paths > 0).count();
+        Ok(Solution::from(p1))
     }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(input: &Input<'_>) -> Result<Solution, Error> {
+        Ok(Solution::from(arrangements(input).sum::<usize>()))
+    }
+}
+
+fn arrangements<'a>(input: &'a Input<'_>) -> impl Iterator<Item = usize> + 'a {
+    input.patterns.iter().map(|pattern| {
+        let mut cache = HashMap::new();
+        dfs(pattern, &input.towels, &mut cache)
+    })
 }
 
 fn dfs<'a>(remaining: &'a [u8], towels: &[&[u8]], cache: &mut Cache<'a>) -> usize {
@@ -52,7 +71,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day19/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day19::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(6));
         assert_eq!(p2, Solution::Usize(16));
     }
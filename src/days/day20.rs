@@ -1,87 +1,111 @@
-use std::collections::VecDeque;
-
 use hashbrown::HashMap;
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::utils::search::{self, Space};
+use crate::utils::vector_2d::Vector2;
+use crate::{Error, Solution, Solver};
 
 const WALL: u8 = b'#';
 const START: u8 = b'S';
 const END: u8 = b'E';
 
-type Vector2 = (usize, usize);
-
-pub fn solve(input: &str) -> SolutionPair {
-    solve_inner(input, 100)
-}
-
-fn solve_inner(input: &str, threshold: usize) -> SolutionPair {
-    let (mut start, mut end) = ((0, 0), (0, 0));
-    let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
-
-    for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
-            match grid[y][x] {
-                START => start = (x, y),
-                END => end = (x, y),
-                _ => {}
+/// Every tile of the track is a key in a map, so keep them small.
+type Tile = Vector2<u16>;
+
+pub struct Day20<const THRESHOLD: usize = 100>;
+
+impl<const THRESHOLD: usize> Solver for Day20<THRESHOLD> {
+    /// How far along the track each tile is.
+    type Input<'a> = HashMap<Tile, usize>;
+
+    fn parse(input: &str) -> Result<HashMap<Tile, usize>, Error> {
+        let (mut start, mut end) = (None, None);
+        let map = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, tile) in line.bytes().enumerate() {
+                let found = match tile {
+                    START => &mut start,
+                    END => &mut end,
+                    _ => continue,
+                };
+                let tile = Vector2::new_usize(x, y).try_cast().ok_or_else(|| {
+                    Error::at(input, &line[x..], "the track is too big for `u16` tiles")
+                })?;
+                *found = Some(tile);
             }
         }
+
+        let track = Track {
+            map: &map,
+            start: start.ok_or_else(|| Error::new("no start `S` on the map"))?,
+            end: end.ok_or_else(|| Error::new("no end `E` on the map"))?,
+        };
+        let search = search::bfs(&track);
+        match search.goal {
+            Some(_) => Ok(search.distances),
+            None => Err(Error::new("no track from `S` to `E`")),
+        }
     }
 
-    let (_steps, distances) = bfs(&grid, start, end);
+    fn part1(distances: &HashMap<Tile, usize>) -> Result<Solution, Error> {
+        Ok(Solution::from(cheats(distances, 2, THRESHOLD)))
+    }
+
+    fn part2(distances: &HashMap<Tile, usize>) -> Result<Solution, Error> {
+        Ok(Solution::from(cheats(distances, 20, THRESHOLD)))
+    }
+}
 
-    let (mut p1, mut p2) = (0_usize, 0_usize);
+#[allow(deprecated)] // itertools 0.15 deprecates tuple_combinations
+fn cheats(distances: &HashMap<Tile, usize>, max_cheat: usize, threshold: usize) -> usize {
+    let mut count = 0;
     for ((&a, &d1), (&b, &d2)) in distances.iter().tuple_combinations() {
-        let d = manhattan(a, b);
+        let d = a.distance_to(b);
 
-        if d <= 20 && threshold <= time_save(d2, d1, d) {
-            if d <= 2 {
-                p1 += 1;
-            }
-            p2 += 1;
+        if d <= max_cheat && threshold <= time_save(d2, d1, d) {
+            count += 1;
         }
     }
-
-    (Solution::from(p1), Solution::from(p2))
+    count
 }
 
 fn time_save(d2: usize, d1: usize, d: usize) -> usize {
     d2.abs_diff(d1) - d
 }
 
-fn manhattan(a: Vector2, b: Vector2) -> usize {
-    let (x1, y1) = a;
-    let (x2, y2) = b;
-
-    x1.abs_diff(x2) + y1.abs_diff(y2)
+/// The single track from `start` to `end`.
+struct Track<'a> {
+    map: &'a [&'a [u8]],
+    start: Tile,
+    end: Tile,
 }
 
-fn bfs(map: &[&[u8]], start: Vector2, end: Vector2) -> (usize, HashMap<Vector2, usize>) {
-    let mut q = VecDeque::from([(0, start)]);
-
-    let mut distances = HashMap::new();
-
-    while let Some((steps, (x, y))) = q.pop_front() {
-        if distances.contains_key(&(x, y)) {
-            continue;
-        }
-        distances.insert((x, y), steps);
+impl Space for Track<'_> {
+    type State = Tile;
 
-        if (x, y) == end {
-            return (steps, distances);
-        }
+    fn start(&self) -> Tile {
+        self.start
+    }
 
-        for (x2, y2) in [(x, y + 1), (x, y - 1), (x + 1, y), (x - 1, y)] {
-            match map.get(y2).and_then(|row| row.get(x2)) {
-                Some(&WALL) => continue,
-                Some(_) => q.push_back((steps + 1, (x2, y2))),
-                _ => continue,
-            }
-        }
+    fn successors(&self, p: &Tile) -> impl IntoIterator<Item = (Tile, usize)> {
+        // rows can differ in length, so anything past the end of a row counts as a wall
+        let width = self.map.iter().map(|row| row.len()).max().unwrap_or(0);
+        let height = self.map.len();
+        let clamp = |n: usize| u16::try_from(n).unwrap_or(u16::MAX);
+        p.adjacent_within(clamp(width), clamp(height))
+            .filter(|next| {
+                let tile = next
+                    .index()
+                    .and_then(|(x, y)| self.map.get(y)?.get(x).copied());
+                tile.is_some_and(|tile| tile != WALL)
+            })
+            .map(|next| (next, 1))
     }
 
-    unreachable!()
+    fn is_goal(&self, &p: &Tile) -> bool {
+        p == self.end
+    }
 }
 
 #[cfg(test)]
@@ -92,8 +116,18 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day20/test.txt");
 
-        let (p1, p2) = super::solve_inner(input, 2);
+        let (p1, p2) = Day20::<2>::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(44));
         assert_eq!(p2, Solution::Usize(3081));
     }
+
+    #[test]
+    fn test_ragged_rows() {
+        // the end sits on a row shorter than the first one
+        let distances = Day20::<2>::parse("#####\n#S..#\n###.\n#E..\n").unwrap();
+        assert_eq!(distances[&Vector2::new(1, 3)], 6);
+
+        let error = Day20::<2>::parse("#S#\n##\n").unwrap_err();
+        assert_eq!(error.to_string(), "no end `E` on the map");
+    }
 }
@@ -1,13 +1,24 @@
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(_input: &str) -> SolutionPair {
-    // Your solution here...
-    let p1 = 0_usize;
-    let p2 = 0_usize;
+pub struct Day21;
 
-    (Solution::from(p1), Solution::from(p2))
+impl Solver for Day21 {
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn part1(_input: &&str) -> Result<Solution, Error> {
+        // Your solution here...
+        Ok(Solution::from(0_usize))
+    }
+
+    fn part2(_input: &&str) -> Result<Solution, Error> {
+        Ok(Solution::from(0_usize))
+    }
 }
 
 #[cfg(test)]
@@ -18,7 +29,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day21/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day21::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(0));
         assert_eq!(p2, Solution::Usize(0));
     }
@@ -1,26 +1,35 @@
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn solve(input: &str) -> SolutionPair {
-    let values = input
-        .lines()
-        .map(str::parse::<usize>)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>();
-
-    let (mut p1, p2) = (0_usize, 0_usize);
-    for value in values {
-        let mut value = value;
-        for _ in 0..2000 {
-            value = prune(mix(value, value * 64));
-            value = prune(mix(value, value / 32));
-            value = prune(mix(value, value * 2048));
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Vec<usize>, Error> {
+        input.lines().map(|l| error::parse(input, l)).collect()
+    }
+
+    fn part1(values: &Vec<usize>) -> Result<Solution, Error> {
+        let mut p1 = 0_usize;
+        for &value in values {
+            let mut value = value;
+            for _ in 0..2000 {
+                value = prune(mix(value, value * 64));
+                value = prune(mix(value, value / 32));
+                value = prune(mix(value, value * 2048));
+            }
+            p1 += value;
         }
-        p1 += value;
+
+        Ok(Solution::from(p1))
     }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(_values: &Vec<usize>) -> Result<Solution, Error> {
+        Ok(Solution::from(0_usize))
+    }
 }
 
 fn mix(a: usize, b: usize) -> usize {
@@ -39,7 +48,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day22/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day22::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(37327623));
         assert_eq!(p2, Solution::Usize(23));
     }
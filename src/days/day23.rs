@@ -1,28 +1,40 @@
 use hashbrown::HashMap;
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
-pub fn solve(input: &str) -> SolutionPair {
-    let graph = parse_graph(input);
+pub struct Day23;
 
-    let p1 = p1(&graph);
-    let p2 = p2(graph);
+impl Solver for Day23 {
+    type Input<'a> = HashMap<&'a str, Vec<&'a str>>;
 
-    (Solution::Usize(p1), Solution::Str(p2))
+    fn parse(input: &str) -> Result<HashMap<&str, Vec<&str>>, Error> {
+        parse_graph(input)
+    }
+
+    fn part1(graph: &HashMap<&str, Vec<&str>>) -> Result<Solution, Error> {
+        Ok(Solution::Usize(p1(graph)))
+    }
+
+    fn part2(graph: &HashMap<&str, Vec<&str>>) -> Result<Solution, Error> {
+        Ok(Solution::Str(p2(graph)))
+    }
 }
 
-fn parse_graph(input: &str) -> HashMap<&str, Vec<&str>> {
+fn parse_graph(input: &str) -> Result<HashMap<&str, Vec<&str>>, Error> {
     let mut graph = HashMap::new();
     for line in input.lines() {
-        let (a, b) = line.split_once('-').unwrap();
+        let (a, b) = line
+            .split_once('-')
+            .ok_or_else(|| Error::on_line(input, line, "expected `a-b`"))?;
 
         graph.entry(a).or_insert_with(Vec::new).push(b);
         graph.entry(b).or_insert_with(Vec::new).push(a);
     }
-    graph
+    Ok(graph)
 }
 
+#[allow(deprecated)] // itertools 0.15 deprecates tuple_combinations
 fn p1(graph: &HashMap<&str, Vec<&str>>) -> usize {
     let mut p1 = 0;
     for (&a, &b, &c) in graph.keys().tuple_combinations() {
@@ -37,7 +49,7 @@ fn p1(graph: &HashMap<&str, Vec<&str>>) -> usize {
     p1
 }
 
-fn p2(graph: HashMap<&str, Vec<&str>>) -> String {
+fn p2(graph: &HashMap<&str, Vec<&str>>) -> String {
     let mut clique = Vec::new();
     let mut largest_clique = Vec::new();
 
@@ -67,7 +79,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day23/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day23::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(7));
         assert_eq!(p2, Solution::Str("co,de,ka,ta".to_string()));
     }
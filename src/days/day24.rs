@@ -54,32 +54,66 @@
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
-use crate::{Solution, SolutionPair};
+use crate::etc::error;
+use crate::{Error, Solution, Solver};
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (s1, s2) = input.split_once("\n\n").unwrap();
+type Gates<'a> = HashMap<&'a str, (&'a str, &'a str, &'a str)>;
 
-    let mut cache = HashMap::new();
-    let mut connections = HashMap::new();
+pub struct Input<'a> {
+    wires: HashMap<&'a str, bool>,
+    connections: Gates<'a>,
+}
 
-    for line in s1.lines() {
-        let (gate, v) = line.split_once(": ").unwrap();
-        cache.insert(gate, v == "1");
-    }
+pub struct Day24;
 
-    for line in s2.lines() {
-        let (l, gate, r, _, dest) = line.split_whitespace().collect_tuple().unwrap();
-        connections.insert(dest, (l, gate, r));
-    }
+impl Solver for Day24 {
+    type Input<'a> = Input<'a>;
+
+    fn parse(input: &str) -> Result<Input<'_>, Error> {
+        let (s1, s2) = input
+            .split_once("\n\n")
+            .ok_or_else(|| Error::new("expected a blank line before the gates"))?;
+
+        let mut wires = HashMap::new();
+        let mut connections = HashMap::new();
+
+        for line in s1.lines() {
+            let (gate, v) = line
+                .split_once(": ")
+                .ok_or_else(|| Error::on_line(input, line, "expected `wire: value`"))?;
+            wires.insert(gate, v == "1");
+        }
+
+        for line in s2.lines() {
+            let (l, gate, r, _, dest) = line
+                .split_whitespace()
+                .collect_tuple()
+                .ok_or_else(|| Error::on_line(input, line, "expected `a OP b -> c`"))?;
+            if !matches!(gate, "AND" | "OR" | "XOR") {
+                return Err(Error::at(input, gate, format!("unknown gate `{}`", gate)));
+            }
+            if let Some(bit) = dest.strip_prefix('z') {
+                error::parse::<u32>(input, bit)?;
+            }
+            connections.insert(dest, (l, gate, r));
+        }
 
-    for key in connections.keys() {
-        compute(&connections, &mut cache, key);
+        Ok(Input { wires, connections })
     }
 
-    let p1 = compute_result(&cache);
-    let p2 = ripple_carry_adder(&connections);
+    fn part1(input: &Input<'_>) -> Result<Solution, Error> {
+        let mut cache = input.wires.clone();
+
+        for key in input.connections.keys() {
+            compute(&input.connections, &mut cache, key);
+        }
+
+        Ok(Solution::from(compute_result(&cache)))
+    }
 
-    (Solution::from(p1), Solution::from(p2))
+    fn part2(input: &Input<'_>) -> Result<Solution, Error> {
+        Ok(Solution::from(ripple_carry_adder(&input.connections)))
+    }
 }
 
 fn ripple_carry_adder(gates: &HashMap<&str, (&str, &str, &str)>) -> String {
@@ -178,7 +212,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day24/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day24::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(2024));
         assert_eq!(p2, Solution::Str("bfw,bqk,ffh,frj,fst,hwm,kpj,kwq,mjb,nrd,rvg,tgd,tnw,vdt,wpb,z02,z03,z05,z06,z07,z08,z10,z11".into()));
     }
@@ -1,36 +1,48 @@
-use crate::{Solution, SolutionPair};
+use crate::{Error, Solution, Solver};
 
 type Key = [u32; 5];
 type Lock = [u32; 5];
 
-pub fn solve(input: &str) -> SolutionPair {
-    let (keys, locks) = parse_input(input);
+pub struct Day25;
 
-    let (mut p1, p2) = (0, Solution::None);
-    for key in &keys {
-        for lock in &locks {
-            let mut valid = true;
-            for column in 0..5 {
-                if key[column] + lock[column] > 5 {
-                    valid = false;
+impl Solver for Day25 {
+    type Input<'a> = (Vec<Key>, Vec<Lock>);
+
+    fn parse(input: &str) -> Result<(Vec<Key>, Vec<Lock>), Error> {
+        parse_input(input)
+    }
+
+    fn part1((keys, locks): &(Vec<Key>, Vec<Lock>)) -> Result<Solution, Error> {
+        let mut p1 = 0;
+        for key in keys {
+            for lock in locks {
+                let mut valid = true;
+                for column in 0..5 {
+                    if key[column] + lock[column] > 5 {
+                        valid = false;
+                    }
+                }
+                if valid {
+                    p1 += 1;
                 }
-            }
-            if valid {
-                p1 += 1;
             }
         }
+
+        Ok(Solution::Usize(p1))
     }
 
-    (Solution::Usize(p1), p2)
+    fn part2(_input: &(Vec<Key>, Vec<Lock>)) -> Result<Solution, Error> {
+        Ok(Solution::None)
+    }
 }
 
-fn parse_input(input: &str) -> (Vec<Key>, Vec<Lock>) {
+fn parse_input(input: &str) -> Result<(Vec<Key>, Vec<Lock>), Error> {
     let mut keys = Vec::new();
     let mut locks = Vec::new();
 
     for schematic in input.split("\n\n") {
-        match schematic.as_bytes()[0] {
-            b'#' => {
+        match schematic.as_bytes().first() {
+            Some(b'#') => {
                 let mut i = 0;
                 let mut key = [0; 5];
                 for &b in schematic.as_bytes().iter().skip(5) {
@@ -45,7 +57,7 @@ fn parse_input(input: &str) -> (Vec<Key>, Vec<Lock>) {
                 }
                 keys.push(key);
             }
-            b'.' => {
+            Some(b'.') => {
                 let mut i = 0;
                 let mut lock = [5; 5];
                 for &b in schematic.as_bytes().iter().skip(5) {
@@ -60,11 +72,14 @@ fn parse_input(input: &str) -> (Vec<Key>, Vec<Lock>) {
                 }
                 locks.push(lock);
             }
-            _ => panic!("Invalid input"),
+            _ => {
+                let reason = "expected a schematic starting with `#` or `.`";
+                return Err(Error::on_line(input, schematic, reason));
+            }
         }
     }
 
-    (keys, locks)
+    Ok((keys, locks))
 }
 
 #[cfg(test)]
@@ -75,7 +90,7 @@ mod tests {
     fn test_sample_input() {
         let input = include_str!("../../input/day25/test.txt");
 
-        let (p1, p2) = super::solve(input);
+        let (p1, p2) = Day25::solve(input).unwrap();
         assert_eq!(p1, Solution::Usize(3));
         assert_eq!(p2, Solution::None);
     }
@@ -0,0 +1,128 @@
+//! Known answers used by `verify` mode.
+//!
+//! Every input can have an answers file next to it, named after the input with an `_answers`
+//! suffix (`real.txt` has `real_answers.txt`). It holds one `part: answer` line per known part:
+//!
+//! ```none
+//! 1: 11
+//! 2: 31
+//! ```
+//!
+//! Answers are compared with the [`Display`](std::fmt::Display) form of a [`Solution`], so the
+//! file does not need to know which integer type a day returns.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::input::{self, Source};
+use super::solution::Solution;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+/// The answers file for a day's input, if the input comes from a file.
+pub fn path(day: u8, source: &Source) -> Option<PathBuf> {
+    let input = match source {
+        Source::Real => input::dir(day).join("real.txt"),
+        Source::Test => input::dir(day).join("test.txt"),
+        Source::Path(path) => path.clone(),
+        Source::Stdin => return None,
+    };
+
+    let stem = input.file_stem()?.to_string_lossy();
+    Some(input.with_file_name(format!("{stem}_answers.txt")))
+}
+
+impl Answers {
+    /// Loads an answers file, treating a missing file as having no known answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents).ok_or_else(|| {
+                let message = format!("malformed answers file {}", path.display());
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let mut answers = Answers::default();
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let (part, answer) = line.split_once(':')?;
+            let answer = Some(answer.trim().to_owned());
+            match part.trim() {
+                "1" => answers.part1 = answer,
+                "2" => answers.part2 = answer,
+                _ => return None,
+            }
+        }
+
+        Some(answers)
+    }
+
+    pub fn check(expected: Option<&str>, actual: &Solution) -> Verdict {
+        match expected {
+            Some(expected) if expected == actual.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("1: 11\n2: a,b,c\n").unwrap();
+        assert_eq!(answers.part1.as_deref(), Some("11"));
+        assert_eq!(answers.part2.as_deref(), Some("a,b,c"));
+
+        let answers = Answers::parse("2: 31").unwrap();
+        assert_eq!(answers.part1, None);
+
+        assert!(Answers::parse("3: 1").is_none());
+        assert!(Answers::parse("11").is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            Answers::check(Some("11"), &Solution::U32(11)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            Answers::check(Some("12"), &Solution::U32(11)),
+            Verdict::Fail {
+                expected: "12".into()
+            }
+        );
+        assert_eq!(Answers::check(None, &Solution::U32(11)), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_path() {
+        let path = path(1, &Source::Real).unwrap();
+        assert!(path.ends_with("input/day01/real_answers.txt"));
+
+        let path = super::path(1, &Source::Path("my/input.txt".into())).unwrap();
+        assert_eq!(path, PathBuf::from("my/input_answers.txt"));
+
+        assert_eq!(super::path(1, &Source::Stdin), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Persisted per-day timings to compare later runs against.
+//!
+//! Baselines are kept in plain `day elapsed_ns` lines under `target/`, one file per build profile
+//! and runner mode so debug builds are never compared with release timings, nor single cold runs
+//! with benchmark medians. Only full runs of the real input are recorded, since timings of a
+//! single part or of the sample input are not comparable.
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct Baseline {
+    timings: BTreeMap<u8, Duration>,
+}
+
+/// How a timing compares with its baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    /// Relative change in percent, positive when slower.
+    pub percent: f64,
+    pub regressed: bool,
+}
+
+/// The profile this runner was built with, named after the directory cargo builds it in.
+const PROFILE: &str = match cfg!(debug_assertions) {
+    true => "debug",
+    false => "release",
+};
+
+impl Baseline {
+    pub fn path(mode: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("target")
+            .join(PROFILE)
+            .join(format!("baseline-{mode}.txt"))
+    }
+
+    /// Loads a baseline, treating a missing file as an empty baseline.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baseline::default()),
+            Err(e) => return Err(e),
+        };
+
+        Self::parse(&contents).ok_or_else(|| {
+            let message = format!("malformed baseline file {}", path.display());
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        })
+    }
+
+    fn parse(contents: &str) -> Option<Self> {
+        let timings = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (day, nanos) = line.split_once(' ')?;
+                Some((
+                    day.parse().ok()?,
+                    Duration::from_nanos(nanos.trim().parse().ok()?),
+                ))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Baseline { timings })
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents = self
+            .timings
+            .iter()
+            .map(|(day, elapsed)| format!("{day} {}\n", elapsed.as_nanos()))
+            .collect::<String>();
+
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, day: u8) -> Option<Duration> {
+        self.timings.get(&day).copied()
+    }
+
+    pub fn record(&mut self, day: u8, elapsed: Duration) {
+        self.timings.insert(day, elapsed);
+    }
+
+    /// Compares `elapsed` with the baseline for `day`. A day is regressed when it is more than
+    /// `threshold` percent slower.
+    pub fn compare(&self, day: u8, elapsed: Duration, threshold: f64) -> Option<Change> {
+        let baseline = self.get(day)?.as_nanos() as f64;
+        if baseline == 0.0 {
+            return None;
+        }
+
+        let percent = (elapsed.as_nanos() as f64 - baseline) / baseline * 100.0;
+        Some(Change {
+            percent,
+            regressed: percent > threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(3, Duration::from_nanos(1500));
+        baseline.record(12, Duration::from_millis(2));
+
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.txt", std::process::id()));
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get(3), Some(Duration::from_nanos(1500)));
+        assert_eq!(loaded.get(12), Some(Duration::from_millis(2)));
+        assert_eq!(loaded.get(4), None);
+
+        assert!(Baseline::parse("3 abc").is_none());
+        assert!(Baseline::load(Path::new("/nonexistent/baseline.txt")).is_ok());
+    }
+
+    #[test]
+    fn test_path() {
+        let path = Baseline::path("bench");
+        assert!(path.ends_with(format!("target/{}/baseline-bench.txt", PROFILE)));
+        assert_ne!(path, Baseline::path("run"));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut baseline = Baseline::default();
+        baseline.record(1, Duration::from_millis(10));
+
+        let slower = baseline
+            .compare(1, Duration::from_millis(12), 10.0)
+            .unwrap();
+        assert!((slower.percent - 20.0).abs() < 1e-9);
+        assert!(slower.regressed);
+
+        let noise = baseline
+            .compare(1, Duration::from_millis(105) / 10, 10.0)
+            .unwrap();
+        assert!(!noise.regressed);
+
+        let faster = baseline.compare(1, Duration::from_millis(5), 10.0).unwrap();
+        assert!((faster.percent + 50.0).abs() < 1e-9);
+        assert!(!faster.regressed);
+
+        assert_eq!(baseline.compare(2, Duration::from_millis(5), 10.0), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Repeated timing of a day's stages.
+//!
+//! A single cold run is noisy, so `bench` mode runs a few untimed warm-up iterations first and
+//! then summarises `iterations` timed runs per stage. Everything is measured with
+//! [`Instant`](std::time::Instant) through [`solver::run`](super::solver::run), so no external
+//! benchmarking crate is needed.
+use std::time::Duration;
+
+use super::solver::Run;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty set of samples.
+    pub fn new(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is needed");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let n = sorted.len();
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+            _ => sorted[n / 2],
+        };
+
+        let nanos = sorted
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = match n {
+            1 => 0.0,
+            _ => nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Timing statistics for each stage of a day, plus the result of the last run.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    pub last: Run,
+    pub parse: Stats,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+/// Calls `run` for `warmup` untimed runs and then `iterations` timed ones, stopping at the first
+/// error. Each call is one complete run, so the caller can give each its own timeout.
+pub fn bench<E>(
+    warmup: usize,
+    iterations: usize,
+    mut run: impl FnMut() -> Result<Run, E>,
+) -> Result<Bench, E> {
+    assert!(iterations > 0, "at least one iteration is needed");
+
+    for _ in 0..warmup {
+        run()?;
+    }
+
+    let runs = (0..iterations)
+        .map(|_| run())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let stats = |stage: fn(&Run) -> Option<Duration>| {
+        let samples = runs.iter().filter_map(stage).collect::<Vec<_>>();
+        (!samples.is_empty()).then(|| Stats::new(&samples))
+    };
+
+    let parse = stats(|run| Some(run.parse)).unwrap();
+    let part1 = stats(|run| run.part1.as_ref().map(|p| p.elapsed));
+    let part2 = stats(|run| run.part2.as_ref().map(|p| p.elapsed));
+
+    Ok(Bench {
+        last: runs.into_iter().last().unwrap(),
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2, 10].map(Duration::from_millis);
+        let stats = Stats::new(&samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(4));
+        // sample variance: (0 + 9 + 1 + 4 + 36) / 4 = 12.5
+        assert_eq!(
+            stats.std_dev,
+            Duration::from_nanos((12.5_f64.sqrt() * 1e6).round() as u64)
+        );
+
+        let stats = Stats::new(&[4, 1, 3, 2].map(Duration::from_millis));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+
+        let stats = Stats::new(&[Duration::from_millis(7)]);
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bench() {
+        let mut calls = 0;
+        let result = bench(3, 5, || {
+            calls += 1;
+            Ok::<_, ()>(Run {
+                parse: Duration::from_millis(calls),
+                part1: None,
+                part2: None,
+                cpu: None,
+            })
+        });
+        let stats = result.unwrap();
+        assert_eq!(calls, 8);
+        assert_eq!(stats.parse.median, Duration::from_millis(6));
+        assert_eq!(stats.part1, None);
+
+        let mut calls = 0;
+        let result = bench(3, 5, || {
+            calls += 1;
+            match calls {
+                2 => Err("timed out"),
+                _ => Ok(Run {
+                    parse: Duration::ZERO,
+                    part1: None,
+                    part2: None,
+                    cpu: None,
+                }),
+            }
+        });
+        assert_eq!(result.unwrap_err(), "timed out");
+        assert_eq!(calls, 2);
+    }
+}
@@ -0,0 +1,359 @@
+//! Command-line parsing for the runner.
+//!
+//! An optional leading mode (`run`, `bench` or `verify`) is followed by the days, given as a comma
+//! or space separated list of numbers and inclusive ranges, for example `3,5,10-14`. With no days
+//! every registered day runs. Parsing is strict: anything that is not understood is an error and the
+//! runner prints [`USAGE`] rather than guessing.
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use super::input::Source;
+use super::report::Format;
+use super::solver::Part;
+use crate::days;
+
+pub const USAGE: &str = "\
+Usage: cargo run --release -- [run|bench|verify] [DAYS...] [OPTIONS]
+
+Modes:
+  run                    Solve each day once and print the answers (default).
+  bench                  Time each stage repeatedly and report min/median/mean/std dev.
+  verify                 Compare the answers with the input's answers file, e.g.
+                         `input/day01/real_answers.txt`, and fail on any mismatch.
+
+Arguments:
+  [DAYS...]              Days to run, e.g. `3`, `3,5,10-14` or `1-5 7`. Defaults to all days.
+
+Options:
+  --part <1|2>           Only run the given part.
+  --input <test|real|PATH>
+                         Input to solve: the real input (default), the sample input or a file.
+                         A file path can only be used with a single day.
+  --stdin                Read the input for a single day from stdin.
+  --warmup <N>           Untimed iterations before measuring in bench mode [default: 3].
+  --iterations <N>       Timed iterations per day in bench mode [default: 20].
+  --format <FORMAT>      Output format: text (default), json, csv or markdown.
+  -j, --jobs <N>         Solve up to N days at once, 0 for one per CPU [default: 1].
+  --timeout <SECONDS>    Give up on a day after SECONDS of wall-clock time, 0 to wait forever
+                         [default: 60]. In bench mode this applies to each run of the day.
+  --threshold <PERCENT>  Flag days more than PERCENT slower than the saved baseline [default: 10].
+  --save-baseline        Save this run's timings as the new baseline.
+  --list                 List the available days and exit.
+  -h, --help             Print this message and exit.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Run,
+    Bench { warmup: usize, iterations: usize },
+    Verify,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub mode: Mode,
+    pub days: Vec<u8>,
+    pub part: Option<Part>,
+    pub source: Source,
+    pub format: Format,
+    pub threshold: f64,
+    pub save_baseline: bool,
+    pub timeout: Option<Duration>,
+    pub jobs: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Options),
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CliError(String);
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err(CliError(format!($($arg)*)))
+    };
+}
+
+/// Parses the arguments that follow the program name.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Command, CliError> {
+    let mut days = Vec::new();
+    let mut part = None;
+    let mut source = None;
+    let mut warmup = None;
+    let mut iterations = None;
+    let mut format = Format::Text;
+    let mut threshold = 10.0;
+    let mut save_baseline = false;
+    let mut timeout = Some(Duration::from_secs(60));
+    let mut jobs = 1;
+
+    let mut args = args.into_iter().peekable();
+    let mode = args.next_if(|arg| matches!(arg.as_str(), "run" | "bench" | "verify"));
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_owned(), Some(value.to_owned()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| match inline.clone().or_else(|| args.next()) {
+            Some(value) => Ok(value),
+            None => Err(CliError(format!("`{name}` needs a value"))),
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "--part" => {
+                part = Some(match value("--part")?.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    other => bail!("invalid part `{other}`, expected 1 or 2"),
+                });
+            }
+            "--input" | "--stdin" if source.is_some() => {
+                bail!("`--input` and `--stdin` can only be given once between them")
+            }
+            "--input" => {
+                source = Some(match value("--input")?.as_str() {
+                    "real" => Source::Real,
+                    "test" => Source::Test,
+                    path => Source::Path(PathBuf::from(path)),
+                });
+            }
+            "--stdin" => source = Some(Source::Stdin),
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    "markdown" | "md" => Format::Markdown,
+                    other => {
+                        bail!("unknown format `{other}`, expected text, json, csv or markdown")
+                    }
+                };
+            }
+            "--threshold" => {
+                threshold = match value("--threshold")?.trim_end_matches('%').parse::<f64>() {
+                    Ok(t) if t >= 0.0 => t,
+                    _ => bail!("invalid threshold, expected a non-negative percentage"),
+                };
+            }
+            "--save-baseline" => save_baseline = true,
+            "-j" | "--jobs" => {
+                jobs = match parse_count("--jobs", &value("--jobs")?, 0)? {
+                    0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+                    n => n,
+                };
+            }
+            "--timeout" => {
+                timeout = match value("--timeout")?.trim_end_matches('s').parse::<f64>() {
+                    Ok(0.0) => None,
+                    Ok(t) if t > 0.0 && t.is_finite() => Some(Duration::from_secs_f64(t)),
+                    _ => bail!("invalid timeout, expected a non-negative number of seconds"),
+                };
+            }
+            "--warmup" => warmup = Some(parse_count("--warmup", &value("--warmup")?, 0)?),
+            "--iterations" => {
+                iterations = Some(parse_count("--iterations", &value("--iterations")?, 1)?);
+            }
+            flag if flag.starts_with('-') => bail!("unknown option `{flag}`"),
+            list => days.extend(parse_days(list)?),
+        }
+    }
+
+    if days.is_empty() {
+        days = days::DAYS.iter().map(|d| d.day).collect();
+    } else {
+        days.sort_unstable();
+        days.dedup();
+    }
+
+    let source = source.unwrap_or(Source::Real);
+    if matches!(source, Source::Path(_) | Source::Stdin) && days.len() != 1 {
+        bail!("an input file or stdin can only be used with a single day");
+    }
+
+    let mode = match mode.as_deref() {
+        Some("bench") => Mode::Bench {
+            warmup: warmup.unwrap_or(3),
+            iterations: iterations.unwrap_or(20),
+        },
+        _ if warmup.is_some() || iterations.is_some() => {
+            bail!("`--warmup` and `--iterations` only apply to bench mode")
+        }
+        Some("verify") if format != Format::Text => {
+            bail!("`--format` does not apply to verify mode")
+        }
+        Some("verify") => Mode::Verify,
+        _ => Mode::Run,
+    };
+
+    Ok(Command::Run(Options {
+        mode,
+        days,
+        part,
+        source,
+        format,
+        threshold,
+        save_baseline,
+        timeout,
+        jobs,
+    }))
+}
+
+fn parse_count(name: &str, value: &str, min: usize) -> Result<usize, CliError> {
+    match value.parse::<usize>() {
+        Ok(n) if n >= min => Ok(n),
+        _ => bail!("invalid value `{value}` for `{name}`, expected a number of at least {min}"),
+    }
+}
+
+fn parse_days(list: &str) -> Result<Vec<u8>, CliError> {
+    let mut days = Vec::new();
+
+    for item in list.split(',').filter(|item| !item.is_empty()) {
+        let (from, to) = match item.split_once('-') {
+            Some((from, to)) => (parse_day(from)?, parse_day(to)?),
+            None => (parse_day(item)?, parse_day(item)?),
+        };
+        if from > to {
+            bail!("invalid day range `{item}`");
+        }
+        days.extend(from..=to);
+    }
+
+    Ok(days)
+}
+
+fn parse_day(day: &str) -> Result<u8, CliError> {
+    match day.parse::<u8>() {
+        Ok(n) if days::get(n).is_some() => Ok(n),
+        _ => bail!(
+            "unknown day `{day}`, available days are {}",
+            days::available()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &str) -> Result<Options, CliError> {
+        match parse(args.split_whitespace().map(String::from))? {
+            Command::Run(options) => Ok(options),
+            command => panic!("expected a run, got {command:?}"),
+        }
+    }
+
+    #[test]
+    fn test_day_lists() {
+        assert_eq!(run("").unwrap().days, (1..=25).collect::<Vec<_>>());
+        assert_eq!(run("3 7").unwrap().days, vec![3, 7]);
+        assert_eq!(
+            run("3,5,10-14").unwrap().days,
+            vec![3, 5, 10, 11, 12, 13, 14]
+        );
+        assert_eq!(run("5,1-3 2").unwrap().days, vec![1, 2, 3, 5]);
+
+        assert!(run("0").is_err());
+        assert!(run("26").is_err());
+        assert!(run("7-3").is_err());
+        assert!(run("1-").is_err());
+        assert!(run("x").is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        let options = run("4 --part 2 --input test").unwrap();
+        assert_eq!(options.part, Some(Part::Two));
+        assert_eq!(options.source, Source::Test);
+
+        let options = run("4 --part=1 --input=foo.txt").unwrap();
+        assert_eq!(options.part, Some(Part::One));
+        assert_eq!(options.source, Source::Path(PathBuf::from("foo.txt")));
+
+        assert_eq!(run("4 --stdin").unwrap().source, Source::Stdin);
+
+        assert!(run("--part 3").is_err());
+        assert!(run("--part").is_err());
+        assert!(run("--stdin").is_err());
+        assert!(run("1,2 --input foo.txt").is_err());
+        assert!(run("4 --stdin --input test").is_err());
+        assert!(run("--verbose").is_err());
+
+        let options = run("--threshold 5% --save-baseline").unwrap();
+        assert_eq!(options.threshold, 5.0);
+        assert!(options.save_baseline);
+        assert_eq!(run("").unwrap().threshold, 10.0);
+        assert!(run("--threshold -1").is_err());
+
+        assert_eq!(run("").unwrap().format, Format::Text);
+        assert_eq!(run("--format json").unwrap().format, Format::Json);
+        assert_eq!(run("--format=csv").unwrap().format, Format::Csv);
+        assert_eq!(run("--format md").unwrap().format, Format::Markdown);
+        assert!(run("--format yaml").is_err());
+
+        assert_eq!(run("").unwrap().timeout, Some(Duration::from_secs(60)));
+        assert_eq!(
+            run("--timeout 1.5").unwrap().timeout,
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            run("--timeout=5s").unwrap().timeout,
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(run("--timeout 0").unwrap().timeout, None);
+        assert!(run("--timeout -1").is_err());
+        assert!(run("--timeout soon").is_err());
+
+        assert_eq!(run("").unwrap().jobs, 1);
+        assert_eq!(run("-j 4").unwrap().jobs, 4);
+        assert_eq!(run("--jobs=2").unwrap().jobs, 2);
+        assert!(run("--jobs 0").unwrap().jobs >= 1);
+        assert!(run("--jobs many").is_err());
+        assert_eq!(parse(["--list".to_owned()]), Ok(Command::List));
+    }
+
+    #[test]
+    fn test_modes() {
+        assert_eq!(run("").unwrap().mode, Mode::Run);
+        assert_eq!(run("run 3").unwrap().days, vec![3]);
+        assert_eq!(
+            run("bench 1-3").unwrap().mode,
+            Mode::Bench {
+                warmup: 3,
+                iterations: 20
+            }
+        );
+        assert_eq!(
+            run("bench --warmup 0 --iterations=5").unwrap().mode,
+            Mode::Bench {
+                warmup: 0,
+                iterations: 5
+            }
+        );
+
+        assert!(run("bench --iterations 0").is_err());
+        assert!(run("3 --warmup 2").is_err());
+        assert!(run("3 bench").is_err());
+
+        assert_eq!(run("verify 1-3").unwrap().mode, Mode::Verify);
+        assert!(run("verify --iterations 3").is_err());
+        assert!(run("verify --format json").is_err());
+    }
+}
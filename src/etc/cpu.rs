@@ -0,0 +1,78 @@
+//! CPU time of the calling thread.
+//!
+//! Every day runs on a thread of its own, so the thread's CPU time is the work the day did, even
+//! when `--jobs` solves several days side by side and their wall-clock times overlap. There is no
+//! `libc` dependency, so `clock_gettime` is declared here for the platforms that have it.
+use std::time::Duration;
+
+/// The CPU time the calling thread has used so far, or `None` where the platform cannot tell.
+pub fn thread_time() -> Option<Duration> {
+    sys::thread_time()
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod sys {
+    use std::os::raw::{c_int, c_long};
+    use std::time::Duration;
+
+    #[cfg(target_os = "linux")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+    #[cfg(target_os = "macos")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 16;
+
+    /// `struct timespec`, whose `time_t` is a `long` on both platforms.
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+
+    extern "C" {
+        fn clock_gettime(clock: c_int, time: *mut Timespec) -> c_int;
+    }
+
+    pub fn thread_time() -> Option<Duration> {
+        let mut time = Timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `time` is a valid timespec that outlives the call.
+        let status = unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut time) };
+        (status == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod sys {
+    use std::time::Duration;
+
+    pub fn thread_time() -> Option<Duration> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Instant;
+
+    #[test]
+    fn test_thread_time() {
+        let before = thread_time().unwrap();
+        let started = Instant::now();
+        let mut x = 0u64;
+        while started.elapsed() < Duration::from_millis(20) {
+            x = std::hint::black_box(x.wrapping_add(1));
+        }
+        assert!(thread_time().unwrap() > before);
+
+        // A sleeping thread uses next to no CPU time.
+        let slept = thread::spawn(|| {
+            let before = thread_time().unwrap();
+            thread::sleep(Duration::from_millis(50));
+            thread_time().unwrap() - before
+        });
+        assert!(slept.join().unwrap() < Duration::from_millis(25));
+    }
+}
@@ -0,0 +1,128 @@
+//! The error a day reports when its input cannot be parsed or a part has no answer.
+//!
+//! Parsing mostly works on slices of the original input, so [`Error::at`] can work out the line
+//! and column of the offending slice from its position alone. The runner fills in the day, which
+//! gives messages like ``day 05, line 1177: expected `a|b` ``.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub day: Option<u8>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl Error {
+    pub fn new(reason: impl Display) -> Self {
+        Error {
+            day: None,
+            line: None,
+            column: None,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// An error at `part`, which must be a slice of `input`. The line and column are 1-based, and
+    /// are left out if `part` does not point into `input`.
+    pub fn at(input: &str, part: &str, reason: impl Display) -> Self {
+        let error = Error::new(reason);
+        let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        if offset > input.len() {
+            return error;
+        }
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Error {
+            line: Some(before.matches('\n').count() + 1),
+            column: Some(before[line_start..].chars().count() + 1),
+            ..error
+        }
+    }
+
+    /// An error about the whole of `line`, a line of `input`.
+    pub fn on_line(input: &str, line: &str, reason: impl Display) -> Self {
+        Error {
+            column: None,
+            ..Error::at(input, line, reason)
+        }
+    }
+
+    /// Sets the day, keeping one that is already set.
+    pub fn for_day(self, day: u8) -> Self {
+        Error {
+            day: self.day.or(Some(day)),
+            ..self
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let location = [
+            self.day.map(|day| format!("day {:02}", day)),
+            self.line.map(|line| format!("line {}", line)),
+            self.column.map(|column| format!("column {}", column)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if !location.is_empty() {
+            write!(f, "{}: ", location.join(", "))?;
+        }
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses `part`, a slice of `input`, reporting where it is when it is not a valid `T`.
+pub fn parse<T>(input: &str, part: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    part.parse()
+        .map_err(|e| Error::at(input, part, format!("invalid value `{}`: {}", part, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let input = "47|53\n97 13\n";
+        let line = input.lines().nth(1).unwrap();
+
+        let error = Error::at(input, line, "expected `a|b`");
+        assert_eq!((error.line, error.column), (Some(2), Some(1)));
+        assert_eq!(error.to_string(), "line 2, column 1: expected `a|b`");
+
+        let error = Error::at(input, &line[3..], "oops").for_day(5);
+        assert_eq!(error.to_string(), "day 05, line 2, column 4: oops");
+        assert_eq!(error.for_day(6).day, Some(5));
+
+        let error = Error::on_line(input, line, "expected `a|b`").for_day(5);
+        assert_eq!(error.to_string(), "day 05, line 2: expected `a|b`");
+
+        assert_eq!(Error::new("no guard").to_string(), "no guard");
+        assert_eq!(Error::at(input, "elsewhere", "oops").line, None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "1 x2";
+        assert_eq!(parse::<u32>(input, &input[..1]), Ok(1));
+
+        let error = parse::<u32>(input, &input[2..]).unwrap_err();
+        assert_eq!(error.column, Some(3));
+        assert_eq!(
+            error.reason,
+            "invalid value `x2`: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Loads the puzzle input for a day.
+//!
+//! Inputs live in `input/dayNN/real.txt` and are not committed, so by default they are read from
+//! disk at runtime and a missing file only fails the day that needs it. Building with the
+//! `embedded-inputs` feature bakes every real input into the binary with `include_str!` instead,
+//! which keeps file I/O out of the picture entirely (and requires all 25 files to be present).
+//! Sample inputs, explicit paths and stdin are always read at runtime.
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Where a day's input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Real,
+    Test,
+    Path(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Unreadable {
+        day: u8,
+        path: PathBuf,
+        source: io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => {
+                write!(
+                    f,
+                    "input missing for day {day} (expected {})",
+                    path.display()
+                )
+            }
+            InputError::Unreadable { day, path, source } => {
+                write!(
+                    f,
+                    "unable to read input for day {day} from {}: {source}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join(format!("day{day:02}"))
+}
+
+pub fn load(day: u8, source: &Source) -> Result<Cow<'static, str>, InputError> {
+    match source {
+        Source::Real => real(day),
+        Source::Test => read(day, dir(day).join("test.txt")),
+        Source::Path(path) => read(day, path.clone()),
+        Source::Stdin => {
+            let mut input = String::new();
+            match io::stdin().read_to_string(&mut input) {
+                Ok(_) => Ok(Cow::Owned(input)),
+                Err(source) => Err(InputError::Unreadable {
+                    day,
+                    path: "<stdin>".into(),
+                    source,
+                }),
+            }
+        }
+    }
+}
+
+fn read(day: u8, path: PathBuf) -> Result<Cow<'static, str>, InputError> {
+    match std::fs::read_to_string(&path) {
+        Ok(input) => Ok(Cow::Owned(input)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { day, path }),
+        Err(source) => Err(InputError::Unreadable { day, path, source }),
+    }
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+fn real(day: u8) -> Result<Cow<'static, str>, InputError> {
+    read(day, dir(day).join("real.txt"))
+}
+
+#[cfg(feature = "embedded-inputs")]
+fn real(day: u8) -> Result<Cow<'static, str>, InputError> {
+    macro_rules! embed {
+        ($($day:literal => $dir:literal),*) => {
+            match day {
+                $($day => Ok(Cow::Borrowed(include_str!(concat!(
+                    "../../input/", $dir, "/real.txt"
+                )))),)*
+                _ => Err(InputError::Missing { day, path: dir(day).join("real.txt") }),
+            }
+        };
+    }
+
+    embed!(
+        1 => "day01", 2 => "day02", 3 => "day03", 4 => "day04", 5 => "day05",
+        6 => "day06", 7 => "day07", 8 => "day08", 9 => "day09", 10 => "day10",
+        11 => "day11", 12 => "day12", 13 => "day13", 14 => "day14", 15 => "day15",
+        16 => "day16", 17 => "day17", 18 => "day18", 19 => "day19", 20 => "day20",
+        21 => "day21", 22 => "day22", 23 => "day23", 24 => "day24", 25 => "day25"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_input() {
+        let input = load(1, &Source::Test).unwrap();
+        assert_eq!(input, include_str!("../../input/day01/test.txt"));
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let error = load(99, &Source::Real).unwrap_err();
+        assert!(matches!(error, InputError::Missing { day: 99, .. }));
+        assert!(error.to_string().starts_with("input missing for day 99"));
+    }
+}
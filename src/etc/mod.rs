@@ -1,2 +1,13 @@
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod cli;
+pub mod cpu;
+pub mod error;
+pub mod input;
+pub mod panic;
+pub mod pool;
+pub mod report;
 pub mod solution;
-
+pub mod solver;
+pub mod watchdog;
@@ -0,0 +1,84 @@
+//! Isolation of days that panic, so one broken day does not abort the whole run.
+//!
+//! Days `unwrap()` and index freely, so an unexpected input usually ends in a panic. [`catch`]
+//! turns such a panic into a [`Panic`] with its message and location. While a panic is being
+//! caught the default hook stays quiet, so the runner reports it once, as that day's result.
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter, Result};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {}: {}", location, self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// Runs `f`, returning the panic it raised instead of unwinding further.
+pub fn catch<T>(f: impl FnOnce() -> T) -> std::result::Result<T, Panic> {
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| match CATCHING.get() {
+            true => LOCATION.set(info.location().map(ToString::to_string)),
+            false => default(info),
+        }));
+    });
+
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+
+    result.map_err(|payload| Panic {
+        message: message(payload.as_ref()),
+        location: LOCATION.take(),
+    })
+}
+
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(catch(|| 11), Ok(11));
+
+        let panic = catch(|| panic!("out of bounds")).unwrap_err();
+        assert_eq!(panic.message, "out of bounds");
+        assert!(panic.location.unwrap().starts_with("src/etc/panic.rs:"));
+
+        let panic = catch(|| -> u8 { panic!("{} is not a digit", 'x') }).unwrap_err();
+        assert_eq!(panic.message, "x is not a digit");
+
+        let panic = catch(|| [1, 2, 3].iter().position(|&x| x == 4).unwrap()).unwrap_err();
+        assert!(panic
+            .to_string()
+            .starts_with("panicked at src/etc/panic.rs:"));
+    }
+}
@@ -0,0 +1,74 @@
+//! A small scoped thread pool for running independent days concurrently.
+//!
+//! Workers take the next item as soon as they are free, so the results arrive out of order. They
+//! are buffered and handed on in the original order, which keeps the output the same no matter
+//! how many jobs run.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` on every item with up to `jobs` threads, passing the results to `emit` in the order
+/// of `items` as soon as each is available.
+pub fn ordered<T, R, W, E>(items: &[T], jobs: usize, work: W, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    E: FnMut(R),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                if sender.send((i, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = (0..items.len()).map(|_| None).collect::<Vec<_>>();
+        let mut emitted = 0;
+        for (i, result) in receiver {
+            pending[i] = Some(result);
+            while let Some(result) = pending.get_mut(emitted).and_then(Option::take) {
+                emit(result);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_ordered() {
+        let items = (0..20).collect::<Vec<u64>>();
+
+        for jobs in [1, 4, 50] {
+            let mut results = Vec::new();
+            ordered(
+                &items,
+                jobs,
+                |&i| {
+                    // Finish the early items last.
+                    thread::sleep(Duration::from_millis(20 - i));
+                    i * i
+                },
+                |result| results.push(result),
+            );
+            assert_eq!(results, items.iter().map(|i| i * i).collect::<Vec<_>>());
+        }
+
+        ordered(&[] as &[u8], 4, |_| (), |_| panic!("nothing to emit"));
+    }
+}
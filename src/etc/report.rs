@@ -0,0 +1,452 @@
+//! Collected per-day results and the formats the runner can write them in.
+//!
+//! [`Text`] is the human-readable output printed while days run. [`Json`], [`Csv`] and
+//! [`Markdown`] are written once every day has finished, for dashboards and README tables. In
+//! bench mode every timing is the median of the measured iterations.
+use std::fmt::{Display, Formatter, Result};
+use std::time::Duration;
+
+use super::baseline::Change;
+use super::bench::{Bench, Stats};
+use super::solution::Solution;
+use super::solver::{Run, Stage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Clone)]
+pub struct Timing {
+    pub elapsed: Duration,
+    pub stats: Option<Stats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Solution,
+    pub timing: Timing,
+}
+
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub parse: Timing,
+    pub parts: Vec<PartReport>,
+    /// CPU time of the whole run, not kept in bench mode where every timing is a median.
+    pub cpu: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub result: std::result::Result<Solved, String>,
+}
+
+impl DayReport {
+    pub fn from_run(day: u8, run: Run) -> Self {
+        let timing = |elapsed| Timing {
+            elapsed,
+            stats: None,
+        };
+        let parts = [(1, run.part1), (2, run.part2)]
+            .into_iter()
+            .filter_map(|(part, stage)| {
+                let Stage { value, elapsed } = stage?;
+                Some(PartReport {
+                    part,
+                    answer: value,
+                    timing: timing(elapsed),
+                })
+            })
+            .collect();
+
+        let parse = timing(run.parse);
+        DayReport {
+            day,
+            result: Ok(Solved {
+                parse,
+                parts,
+                cpu: run.cpu,
+            }),
+        }
+    }
+
+    pub fn from_bench(day: u8, bench: Bench) -> Self {
+        let timing = |stats: Stats| Timing {
+            elapsed: stats.median,
+            stats: Some(stats),
+        };
+        let answers = [bench.last.part1, bench.last.part2];
+        let parts = [bench.part1, bench.part2]
+            .into_iter()
+            .zip(answers)
+            .zip(1..)
+            .filter_map(|((stats, stage), part)| {
+                let answer = stage?.value;
+                Some(PartReport {
+                    part,
+                    answer,
+                    timing: timing(stats?),
+                })
+            })
+            .collect();
+
+        let parse = timing(bench.parse);
+        DayReport {
+            day,
+            result: Ok(Solved {
+                parse,
+                parts,
+                cpu: None,
+            }),
+        }
+    }
+
+    pub fn failed(day: u8, reason: impl Display) -> Self {
+        DayReport {
+            day,
+            result: Err(reason.to_string()),
+        }
+    }
+
+    /// The total time of every stage, zero for a day that failed.
+    pub fn elapsed(&self) -> Duration {
+        match &self.result {
+            Ok(solved) => {
+                let parts = solved.parts.iter().map(|p| p.timing.elapsed);
+                solved.parse.elapsed + parts.sum::<Duration>()
+            }
+            Err(_) => Duration::ZERO,
+        }
+    }
+
+    /// The CPU time of the run, zero for a day that failed and `None` if it was not measured.
+    pub fn cpu_time(&self) -> Option<Duration> {
+        match &self.result {
+            Ok(solved) => solved.cpu,
+            Err(_) => Some(Duration::ZERO),
+        }
+    }
+
+    fn part(&self, part: u8) -> Option<&PartReport> {
+        let solved = self.result.as_ref().ok()?;
+        solved.parts.iter().find(|p| p.part == part)
+    }
+}
+
+pub fn as_ms(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000_000.0
+}
+
+/// One day in the runner's human-readable format, with an optional comparison to the baseline.
+pub struct Text<'a>(pub &'a DayReport, pub Option<Change>);
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let Text(report, change) = self;
+        writeln!(f, "\n=== Day {:02} ===", report.day)?;
+
+        let solved = match &report.result {
+            Ok(solved) => solved,
+            Err(reason) => return writeln!(f, "  · {}", reason),
+        };
+
+        let bench = solved.parse.stats.is_some();
+        if bench {
+            for part in &solved.parts {
+                writeln!(f, "  · Part {}: {}", part.part, part.answer)?;
+            }
+
+            let parts = solved
+                .parts
+                .iter()
+                .map(|p| (format!("Part {}:", p.part), &p.timing));
+            for (name, timing) in [("Parse:".to_owned(), &solved.parse)]
+                .into_iter()
+                .chain(parts)
+            {
+                let Some(stats) = &timing.stats else { continue };
+                writeln!(
+                    f,
+                    "  · {:<7} min {:.4} ms · median {:.4} ms · mean {:.4} ms · σ {:.4} ms",
+                    name,
+                    as_ms(stats.min),
+                    as_ms(stats.median),
+                    as_ms(stats.mean),
+                    as_ms(stats.std_dev),
+                )?;
+            }
+        } else {
+            writeln!(f, "  · Parse: {:.4} ms", as_ms(solved.parse.elapsed))?;
+            for part in &solved.parts {
+                let elapsed = as_ms(part.timing.elapsed);
+                writeln!(
+                    f,
+                    "  · Part {}: {} ({:.4} ms)",
+                    part.part, part.answer, elapsed
+                )?;
+            }
+        }
+
+        let label = if bench { "Elapsed (median)" } else { "Elapsed" };
+        write!(f, "  · {}: {:.4} ms", label, as_ms(report.elapsed()))?;
+        match change {
+            Some(Change {
+                percent,
+                regressed: true,
+            }) => {
+                writeln!(f, " ({:+.1}% vs baseline, regressed)", percent)
+            }
+            Some(Change { percent, .. }) => writeln!(f, " ({:+.1}% vs baseline)", percent),
+            None => writeln!(f),
+        }
+    }
+}
+
+/// All days as a JSON document.
+pub struct Json<'a>(pub &'a [DayReport]);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        fn timing(f: &mut Formatter<'_>, timing: &Timing) -> Result {
+            write!(f, "{{\"elapsed_ms\": {}", as_ms(timing.elapsed))?;
+            if let Some(stats) = &timing.stats {
+                write!(
+                    f,
+                    ", \"min_ms\": {}, \"median_ms\": {}, \"mean_ms\": {}, \"std_dev_ms\": {}",
+                    as_ms(stats.min),
+                    as_ms(stats.median),
+                    as_ms(stats.mean),
+                    as_ms(stats.std_dev),
+                )?;
+            }
+            write!(f, "}}")
+        }
+
+        writeln!(f, "{{")?;
+        writeln!(f, "  \"days\": [")?;
+        for (i, report) in self.0.iter().enumerate() {
+            write!(f, "    {{\"day\": {}, ", report.day)?;
+            match &report.result {
+                Ok(solved) => {
+                    write!(f, "\"parse\": ")?;
+                    timing(f, &solved.parse)?;
+                    write!(f, ", \"parts\": [")?;
+                    for (j, part) in solved.parts.iter().enumerate() {
+                        let separator = if j == 0 { "" } else { ", " };
+                        write!(
+                            f,
+                            "{}{{\"part\": {}, \"answer\": {}, \"variant\": \"{}\", \"timing\": ",
+                            separator,
+                            part.part,
+                            json_string(&part.answer.to_string()),
+                            part.answer.variant(),
+                        )?;
+                        timing(f, &part.timing)?;
+                        write!(f, "}}")?;
+                    }
+                    write!(f, "], \"elapsed_ms\": {}}}", as_ms(report.elapsed()))?;
+                }
+                Err(reason) => write!(f, "\"error\": {}}}", json_string(reason))?,
+            }
+            writeln!(f, "{}", if i + 1 < self.0.len() { "," } else { "" })?;
+        }
+        writeln!(f, "  ],")?;
+
+        let total = self.0.iter().map(DayReport::elapsed).sum::<Duration>();
+        writeln!(f, "  \"total_ms\": {}", as_ms(total))?;
+        writeln!(f, "}}")
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// One row per part, with the parse time repeated on each row of a day.
+pub struct Csv<'a>(pub &'a [DayReport]);
+
+impl Display for Csv<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "day,part,answer,variant,parse_ms,part_ms,error")?;
+        for report in self.0 {
+            match &report.result {
+                Ok(solved) => {
+                    for part in &solved.parts {
+                        writeln!(
+                            f,
+                            "{},{},{},{},{},{},",
+                            report.day,
+                            part.part,
+                            csv_field(&part.answer.to_string()),
+                            part.answer.variant(),
+                            as_ms(solved.parse.elapsed),
+                            as_ms(part.timing.elapsed),
+                        )?;
+                    }
+                }
+                Err(reason) => writeln!(f, "{},,,,,,{}", report.day, csv_field(reason))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// A results table ready to paste into a README.
+pub struct Markdown<'a>(pub &'a [DayReport]);
+
+impl Display for Markdown<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(
+            f,
+            "| Day | Part 1 | Part 2 | Parse (ms) | Part 1 (ms) | Part 2 (ms) | Total (ms) |"
+        )?;
+        writeln!(
+            f,
+            "|----:|-------:|-------:|-----------:|------------:|------------:|-----------:|"
+        )?;
+
+        for report in self.0 {
+            let solved = match &report.result {
+                Ok(solved) => solved,
+                Err(reason) => {
+                    let reason = reason.replace('|', "\\|");
+                    writeln!(f, "| {:02} | {} | | | | | |", report.day, reason)?;
+                    continue;
+                }
+            };
+
+            let answer = |part| match report.part(part) {
+                Some(p) => code_span(&p.answer.to_string()),
+                None => "–".to_owned(),
+            };
+            let elapsed = |part| match report.part(part) {
+                Some(p) => format!("{:.4}", as_ms(p.timing.elapsed)),
+                None => "–".to_owned(),
+            };
+
+            writeln!(
+                f,
+                "| {:02} | {} | {} | {:.4} | {} | {} | {:.4} |",
+                report.day,
+                answer(1),
+                answer(2),
+                as_ms(solved.parse.elapsed),
+                elapsed(1),
+                elapsed(2),
+                as_ms(report.elapsed()),
+            )?;
+        }
+
+        let total = self.0.iter().map(DayReport::elapsed).sum::<Duration>();
+        writeln!(f, "| **Total** | | | | | | **{:.4}** |", as_ms(total))
+    }
+}
+
+/// `s` as inline code inside a table cell. The fence is one backtick longer than the longest run
+/// of backticks in `s`, and pipes are escaped so they do not split the cell.
+fn code_span(s: &str) -> String {
+    let longest = s.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest + 1);
+    // A space keeps a backtick at either end from joining the fence, and is stripped when shown.
+    let padding = match s.starts_with('`') || s.ends_with('`') {
+        true => " ",
+        false => "",
+    };
+    let s = s.replace('|', "\\|");
+    format!("{fence}{padding}{s}{padding}{fence}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports() -> Vec<DayReport> {
+        let stage = |value: Solution, micros| Stage {
+            value,
+            elapsed: Duration::from_micros(micros),
+        };
+        let run = Run {
+            parse: Duration::from_micros(500),
+            part1: Some(stage(Solution::U32(11), 250)),
+            part2: Some(stage(Solution::Str("5,7,\"3\"".into()), 1250)),
+            cpu: None,
+        };
+
+        vec![
+            DayReport::from_run(1, run),
+            DayReport::failed(2, "input missing for day 2"),
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        let json = Json(&reports()).to_string();
+
+        assert!(json.contains("\"day\": 1, \"parse\": {\"elapsed_ms\": 0.5}"));
+        assert!(json.contains(
+            "{\"part\": 1, \"answer\": \"11\", \"variant\": \"U32\", \"timing\": {\"elapsed_ms\": 0.25}}"
+        ));
+        assert!(json.contains("\"answer\": \"5,7,\\\"3\\\"\", \"variant\": \"Str\""));
+        assert!(json.contains("{\"day\": 2, \"error\": \"input missing for day 2\"}\n"));
+        assert!(json.contains("\"total_ms\": 2"));
+    }
+
+    #[test]
+    fn test_csv() {
+        let csv = Csv(&reports()).to_string();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "day,part,answer,variant,parse_ms,part_ms,error");
+        assert_eq!(lines[1], "1,1,11,U32,0.5,0.25,");
+        assert_eq!(lines[2], "1,2,\"5,7,\"\"3\"\"\",Str,0.5,1.25,");
+        assert_eq!(lines[3], "2,,,,,,input missing for day 2");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = Markdown(&reports()).to_string();
+        let lines = markdown.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines[2],
+            "| 01 | `11` | `5,7,\"3\"` | 0.5000 | 0.2500 | 1.2500 | 2.0000 |"
+        );
+        assert_eq!(lines[3], "| 02 | input missing for day 2 | | | | | |");
+        assert_eq!(lines[4], "| **Total** | | | | | | **2.0000** |");
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code_span("11"), "`11`");
+        assert_eq!(code_span("a`b"), "``a`b``");
+        assert_eq!(code_span("``x`"), "``` ``x` ```");
+        assert_eq!(code_span("a|b"), "`a\\|b`");
+    }
+}
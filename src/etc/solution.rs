@@ -19,6 +19,28 @@ pub enum Solution {
     Str(String),
 }
 
+impl Solution {
+    /// The name of the variant, e.g. `"U32"` or `"Str"`.
+    pub fn variant(&self) -> &'static str {
+        match self {
+            I8(_) => "I8",
+            I16(_) => "I16",
+            I32(_) => "I32",
+            I64(_) => "I64",
+            I128(_) => "I128",
+            Isize(_) => "Isize",
+            U8(_) => "U8",
+            U16(_) => "U16",
+            U32(_) => "U32",
+            U64(_) => "U64",
+            U128(_) => "U128",
+            Usize(_) => "Usize",
+            Str(_) => "Str",
+            None => "None",
+        }
+    }
+}
+
 impl Display for Solution {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
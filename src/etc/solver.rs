@@ -0,0 +1,94 @@
+//! Splits a day into separate `parse`, `part1` and `part2` stages so the runner can time each
+//! stage on its own and run a single part.
+//!
+//! The parsed input is an associated type that may borrow from the raw input, which lets days
+//! keep `&str` or `&[u8]` slices around instead of copying them. Every stage can fail with an
+//! [`Error`]: parsing when the raw input is malformed, and solving when a well-formed input has
+//! no answer, like a maze without a way out.
+use std::time::{Duration, Instant};
+
+use super::cpu;
+use super::error::Error;
+use super::solution::Solution;
+use crate::SolutionPair;
+
+pub trait Solver {
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+    fn part1(input: &Self::Input<'_>) -> Result<Solution, Error>;
+    fn part2(input: &Self::Input<'_>) -> Result<Solution, Error>;
+
+    /// Parses and solves both parts in one go, as used by the sample tests.
+    #[allow(dead_code)] // only the sample tests call it
+    fn solve(input: &str) -> Result<SolutionPair, Error> {
+        let input = Self::parse(input)?;
+        Ok((Self::part1(&input)?, Self::part2(&input)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone)]
+pub struct Stage<T> {
+    pub value: T,
+    pub elapsed: Duration,
+}
+
+impl<T> Stage<T> {
+    /// Times a stage that may fail, keeping its value only if it succeeds.
+    fn time(f: impl FnOnce() -> Result<T, Error>) -> Result<Self, Error> {
+        let time = Instant::now();
+        let value = f()?;
+        Ok(Stage {
+            value,
+            elapsed: time.elapsed(),
+        })
+    }
+}
+
+/// A registered day: its number and the type-erased [`run`] of its [`Solver`].
+pub struct Day {
+    pub day: u8,
+    pub run: fn(&str, Option<Part>) -> Result<Run, Error>,
+}
+
+/// The outcome of running one day. A part that was not requested is `None`.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub parse: Duration,
+    pub part1: Option<Stage<Solution>>,
+    pub part2: Option<Stage<Solution>>,
+    /// CPU time of every stage together, `None` where the platform cannot measure it.
+    pub cpu: Option<Duration>,
+}
+
+/// Runs the stages of `S` on `input`, timing each of them. `part` selects a single part, `None`
+/// runs both.
+pub fn run<S: Solver>(input: &str, part: Option<Part>) -> Result<Run, Error> {
+    let cpu_start = cpu::thread_time();
+    let parsed = Stage::time(|| S::parse(input))?;
+    let wanted = |p| part.is_none() || part == Some(p);
+
+    let part1 = match wanted(Part::One) {
+        true => Some(Stage::time(|| S::part1(&parsed.value))?),
+        false => None,
+    };
+    let part2 = match wanted(Part::Two) {
+        true => Some(Stage::time(|| S::part2(&parsed.value))?),
+        false => None,
+    };
+
+    Ok(Run {
+        parse: parsed.elapsed,
+        part1,
+        part2,
+        cpu: cpu::thread_time()
+            .zip(cpu_start)
+            .map(|(end, start)| end - start),
+    })
+}
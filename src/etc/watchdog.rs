@@ -0,0 +1,92 @@
+//! Runs a day on its own thread and gives up on it after a wall-clock timeout.
+//!
+//! A thread cannot be killed, so a day that times out keeps spinning in the background until the
+//! runner exits. Days after it still get their answers, but their timings may be skewed by the
+//! stray thread.
+use std::fmt::{Display, Formatter, Result};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use super::error::Error;
+use super::panic::{self, Panic};
+
+/// Same as the main thread, since some days recurse deeply.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Invalid(Error),
+    Panicked(Panic),
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Failure::Invalid(error) => error.fmt(f),
+            Failure::Panicked(panic) => panic.fmt(f),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {:?}", timeout),
+        }
+    }
+}
+
+/// Runs `f` for the given day on a watched thread. `None` waits for as long as it takes.
+pub fn watch<T, F>(day: u8, timeout: Option<Duration>, f: F) -> std::result::Result<T, Failure>
+where
+    T: Send + 'static,
+    F: FnOnce() -> std::result::Result<T, Error> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("day {:02}", day))
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // The runner may have stopped listening after a timeout.
+            let _ = sender.send(panic::catch(f));
+        })
+        .expect("unable to spawn a thread for the day");
+
+    let result = match timeout {
+        Some(timeout) => match receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => return Err(Failure::TimedOut(timeout)),
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the day always sends a result"),
+        },
+        None => receiver.recv().expect("the day always sends a result"),
+    };
+
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => Err(Failure::Invalid(error.for_day(day))),
+        Err(panic) => Err(Failure::Panicked(panic)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch() {
+        assert_eq!(watch(1, None, || Ok(11)), Ok(11));
+        assert_eq!(watch(1, Some(Duration::from_secs(60)), || Ok(11)), Ok(11));
+
+        let timeout = Duration::from_millis(10);
+        let result = watch(1, Some(timeout), || {
+            thread::sleep(Duration::from_secs(1));
+            Ok(())
+        });
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+
+        let result = watch(5, None, || Err::<(), _>(Error::new("expected `a|b`")));
+        assert_eq!(result.unwrap_err().to_string(), "day 05: expected `a|b`");
+
+        match watch(1, None, || -> std::result::Result<(), Error> {
+            panic!("out of bounds")
+        }) {
+            Err(Failure::Panicked(panic)) => assert_eq!(panic.message, "out of bounds"),
+            result => panic!("expected a panic, got {:?}", result),
+        }
+    }
+}
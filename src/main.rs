@@ -2,107 +2,316 @@ mod days;
 mod etc;
 mod utils;
 
-use days::{
-    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
-    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
-};
+use etc::answers::{self, Answers, Verdict};
+use etc::baseline::{Baseline, Change};
+use etc::bench;
+use etc::cli::{self, Command, Mode, Options};
+use etc::error::Error;
+use etc::input::{self, InputError, Source};
+use etc::pool;
+use etc::report::{as_ms, Csv, DayReport, Format, Json, Markdown, Text};
 use etc::solution::Solution;
-use std::env;
-use std::time::Instant;
+use etc::solver::{Day, Run, Solver};
+use etc::watchdog::{self, Failure};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{env, io, process};
 
 pub type SolutionPair = (Solution, Solution);
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let options = match cli::parse(env::args().skip(1)) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::List) => {
+            println!("Available days: {}", days::available());
+            return;
+        }
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            process::exit(2);
+        }
+    };
 
-    let days: Vec<u8> = match args.len() {
-        0..=1 => (1..=25).collect(),
-        2 => {
-            let val = args[1].parse().unwrap();
-            vec![val]
+    let ok = match options.mode {
+        Mode::Run => time(&options, None),
+        Mode::Bench { warmup, iterations } => time(&options, Some((warmup, iterations))),
+        Mode::Verify => verify(&options),
+    };
+    if !ok {
+        process::exit(1);
+    }
+}
+
+/// Solves every day once, or benchmarks it with `(warmup, iterations)`, and reports the timings.
+/// Returns whether every day finished without panicking or timing out.
+fn time(options: &Options, bench: Option<(usize, usize)>) -> bool {
+    // Timings of a single part or of another input are not comparable with the baseline.
+    let tracked = options.part.is_none() && options.source == Source::Real;
+    let baseline_path = Baseline::path(if bench.is_some() { "bench" } else { "run" });
+    let mut baseline = match Baseline::load(&baseline_path) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("warning: ignoring baseline: {}", e);
+            Baseline::default()
         }
-        _ => (1..=args.iter().last().unwrap().parse().unwrap()).collect(),
     };
 
-    let mut runtime = 0.0;
+    let mut reports = Vec::new();
+    let mut regressions = Vec::new();
+    let mut failures = Vec::new();
 
-    for day in days {
-        let func = get_day_solver(day);
+    let entries = options
+        .days
+        .iter()
+        .filter_map(|&day| days::get(day))
+        .collect::<Vec<_>>();
+    let started = Instant::now();
 
-        let time = Instant::now();
-        let input = get_input(day);
-        let (p1, p2) = func(input);
-        let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
+    let work = |&entry: &&'static Day| solve(entry, options, bench);
+    pool::ordered(&entries, options.jobs, work, |(report, failure)| {
+        let day = report.day;
+        if let Some(failure) = failure {
+            failures.push((day, failure));
+        }
 
-        println!("\n=== Day {:02} ===", day);
-        println!("  · Part 1: {}", p1);
-        println!("  · Part 2: {}", p2);
-        println!("  · Elapsed: {:.4} ms", elapsed_ms);
+        let tracked = tracked && report.result.is_ok();
+        let change = match tracked {
+            true => baseline.compare(day, report.elapsed(), options.threshold),
+            false => None,
+        };
+        if let Some(Change {
+            percent,
+            regressed: true,
+        }) = change
+        {
+            regressions.push((day, percent));
+        }
+        if tracked && (options.save_baseline || baseline.get(day).is_none()) {
+            baseline.record(day, report.elapsed());
+        }
+
+        if options.format == Format::Text {
+            print!("{}", Text(&report, change));
+        }
+        reports.push(report);
+    });
+
+    let wall_clock = as_ms(started.elapsed());
+    let runtime = as_ms(reports.iter().map(DayReport::elapsed).sum());
+    let cpu_time = reports
+        .iter()
+        .map(DayReport::cpu_time)
+        .sum::<Option<Duration>>();
+    match options.format {
+        Format::Text if bench.is_some() => {
+            println!("Total runtime (sum of per-day medians): {:.4} ms", runtime)
+        }
+        Format::Text => match cpu_time {
+            Some(cpu_time) => println!(
+                "Total runtime (sum of per-day CPU time): {:.4} ms",
+                as_ms(cpu_time)
+            ),
+            // Without a thread CPU clock the closest stand-in is each day's own elapsed time.
+            None => println!(
+                "Total runtime (sum of per-day elapsed time): {:.4} ms",
+                runtime
+            ),
+        },
+        Format::Json => print!("{}", Json(&reports)),
+        Format::Csv => print!("{}", Csv(&reports)),
+        Format::Markdown => print!("{}", Markdown(&reports)),
+    }
+    if options.format == Format::Text {
+        let jobs = match options.jobs {
+            1 => "1 job".to_owned(),
+            n => format!("{} jobs", n),
+        };
+        println!("Elapsed wall-clock time: {:.4} ms ({})", wall_clock, jobs);
+    }
+
+    if !regressions.is_empty() {
+        let days = regressions
+            .iter()
+            .map(|(day, percent)| format!("day {:02} ({:+.1}%)", day, percent))
+            .collect::<Vec<_>>();
+        let summary = format!(
+            "Regressed by more than {}%: {}",
+            options.threshold,
+            days.join(", ")
+        );
+
+        // Keep machine-readable output on stdout parseable.
+        match options.format {
+            Format::Text => println!("{}", summary),
+            _ => eprintln!("{}", summary),
+        }
+    }
+
+    if !failures.is_empty() {
+        match options.format {
+            Format::Text => print!("{}", failure_summary(&failures)),
+            _ => eprint!("{}", failure_summary(&failures)),
+        }
+    }
+
+    if tracked {
+        if let Err(e) = baseline.save(&baseline_path) {
+            eprintln!(
+                "warning: unable to save baseline to {}: {}",
+                baseline_path.display(),
+                e
+            );
+        }
+    }
+
+    failures.is_empty()
+}
 
-        runtime += elapsed_ms;
+/// Solves or benchmarks a single day, along with what went wrong if it did not finish.
+fn solve(
+    entry: &'static Day,
+    options: &Options,
+    bench: Option<(usize, usize)>,
+) -> (DayReport, Option<Failure>) {
+    let day = entry.day;
+    let input = match input::load(day, &options.source) {
+        Ok(input) => input,
+        Err(e) => return (DayReport::failed(day, e), None),
+    };
+
+    let (part, timeout) = (options.part, options.timeout);
+    let result = match bench {
+        None => watchdog::watch(day, timeout, move || (entry.run)(&input, part))
+            .map(|run| DayReport::from_run(day, run)),
+        // Every run gets the whole timeout, so a slow day is not cut short by its iterations.
+        Some((warmup, iterations)) => {
+            let input = Arc::<str>::from(input);
+            bench::bench(warmup, iterations, || {
+                let input = Arc::clone(&input);
+                watchdog::watch(day, timeout, move || (entry.run)(&input, part))
+            })
+            .map(|bench| DayReport::from_bench(day, bench))
+        }
+    };
+
+    match result {
+        Ok(report) => (report, None),
+        Err(failure) => (DayReport::failed(day, &failure), Some(failure)),
     }
+}
 
-    println!("Total runtime: {:.4} ms", runtime);
+/// What checking a day in `verify` mode came to.
+enum Checked {
+    Skipped(InputError),
+    Unreadable(io::Error),
+    Failed(Failure),
+    Solved(Run, Answers),
 }
 
-fn get_input(day: u8) -> &'static str {
-    match day {
-        1 => include_str!("../input/day01/real.txt"),
-        2 => include_str!("../input/day02/real.txt"),
-        3 => include_str!("../input/day03/real.txt"),
-        4 => include_str!("../input/day04/real.txt"),
-        5 => include_str!("../input/day05/real.txt"),
-        6 => include_str!("../input/day06/real.txt"),
-        7 => include_str!("../input/day07/real.txt"),
-        8 => include_str!("../input/day08/real.txt"),
-        9 => include_str!("../input/day09/real.txt"),
-        10 => include_str!("../input/day10/real.txt"),
-        11 => include_str!("../input/day11/real.txt"),
-        12 => include_str!("../input/day12/real.txt"),
-        13 => include_str!("../input/day13/real.txt"),
-        14 => include_str!("../input/day14/real.txt"),
-        15 => include_str!("../input/day15/real.txt"),
-        16 => include_str!("../input/day16/real.txt"),
-        17 => include_str!("../input/day17/real.txt"),
-        18 => include_str!("../input/day18/real.txt"),
-        19 => include_str!("../input/day19/real.txt"),
-        20 => include_str!("../input/day20/real.txt"),
-        21 => include_str!("../input/day21/real.txt"),
-        22 => include_str!("../input/day22/real.txt"),
-        23 => include_str!("../input/day23/real.txt"),
-        24 => include_str!("../input/day24/real.txt"),
-        25 => include_str!("../input/day25/real.txt"),
-        _ => unreachable!(),
+/// Checks every day's answers against its answers file. Returns whether nothing failed.
+fn verify(options: &Options) -> bool {
+    let (mut passed, mut failed, mut unknown, mut skipped) = (0, 0, 0, 0);
+    let mut failures = Vec::new();
+
+    let entries = options
+        .days
+        .iter()
+        .filter_map(|&day| days::get(day))
+        .collect::<Vec<_>>();
+
+    let work = |&entry: &&'static Day| (entry.day, check(entry, options));
+    pool::ordered(&entries, options.jobs, work, |(day, checked)| {
+        println!("\n=== Day {:02} ===", day);
+
+        let (run, answers) = match checked {
+            Checked::Skipped(e) => {
+                println!("  · skipped: {}", e);
+                skipped += 1;
+                return;
+            }
+            Checked::Unreadable(e) => {
+                println!("  · {}", e);
+                failed += 1;
+                return;
+            }
+            Checked::Failed(failure) => {
+                println!("  · {}", failure);
+                failed += 1;
+                failures.push((day, failure));
+                return;
+            }
+            Checked::Solved(run, answers) => (run, answers),
+        };
+
+        let parts = [
+            (1, &run.part1, &answers.part1),
+            (2, &run.part2, &answers.part2),
+        ];
+        for (part, stage, expected) in parts {
+            let Some(stage) = stage else { continue };
+
+            match Answers::check(expected.as_deref(), &stage.value) {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("  · Part {}: pass ({})", part, stage.value);
+                }
+                Verdict::Fail { expected } => {
+                    failed += 1;
+                    println!("  · Part {}: FAIL", part);
+                    println!("      expected: {}", expected);
+                    println!("      actual:   {}", stage.value);
+                }
+                Verdict::Unknown => {
+                    unknown += 1;
+                    println!("  · Part {}: unknown ({})", part, stage.value);
+                }
+            }
+        }
+    });
+
+    println!(
+        "Verified: {} passed, {} failed, {} unknown, {} days skipped",
+        passed, failed, unknown, skipped
+    );
+    if !failures.is_empty() {
+        print!("{}", failure_summary(&failures));
     }
+
+    failed == 0
 }
 
-fn get_day_solver(day: u8) -> fn(&str) -> SolutionPair {
-    match day {
-        1 => day01::solve,
-        2 => day02::solve,
-        3 => day03::solve,
-        4 => day04::solve,
-        5 => day05::solve,
-        6 => day06::solve,
-        7 => day07::solve,
-        8 => day08::solve,
-        9 => day09::solve,
-        10 => day10::solve,
-        11 => day11::solve,
-        12 => day12::solve,
-        13 => day13::solve,
-        14 => day14::solve,
-        15 => day15::solve,
-        16 => day16::solve,
-        17 => day17::solve,
-        18 => day18::solve,
-        19 => day19::solve,
-        20 => day20::solve,
-        21 => day21::solve,
-        22 => day22::solve,
-        23 => day23::solve,
-        24 => day24::solve,
-        25 => day25::solve,
-        _ => unimplemented!(),
+fn check(entry: &'static Day, options: &Options) -> Checked {
+    let day = entry.day;
+    let input = match input::load(day, &options.source) {
+        Ok(input) => input,
+        Err(e) => return Checked::Skipped(e),
+    };
+
+    let answers = match answers::path(day, &options.source).map(|p| Answers::load(&p)) {
+        Some(Ok(answers)) => answers,
+        Some(Err(e)) => return Checked::Unreadable(e),
+        None => Answers::default(),
+    };
+
+    let part = options.part;
+    match watchdog::watch(day, options.timeout, move || (entry.run)(&input, part)) {
+        Ok(run) => Checked::Solved(run, answers),
+        Err(failure) => Checked::Failed(failure),
     }
-}
\ No newline at end of file
+}
+
+fn failure_summary(failures: &[(u8, Failure)]) -> String {
+    let days = failures
+        .iter()
+        .map(|(day, failure)| match failure {
+            // Errors already say which day they are about.
+            Failure::Invalid(error) => format!("  · {}\n", error),
+            failure => format!("  · day {:02}: {}\n", day, failure),
+        })
+        .collect::<String>();
+    format!("Failed:\n{}", days)
+}
@@ -1,5 +1,13 @@
 pub mod vector_2d;
 pub mod vector_3d;
+pub mod grid;
 pub mod iter;
 pub mod parse;
-pub mod integer;
\ No newline at end of file
+pub mod integer;
+pub mod search;
+pub mod dsu;
+pub mod bisect;
+pub mod cycle;
+pub mod math;
+pub mod linalg;
+pub mod grid_3d;
\ No newline at end of file
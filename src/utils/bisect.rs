@@ -0,0 +1,156 @@
+//! Binary search for the first value where a monotone predicate becomes true.
+//!
+//! The predicate must be `false` for some prefix of the range and `true` for the rest, as in
+//! "the first byte after which the exit is blocked". Only about `log2(n)` values are tested.
+//!
+//! When testing a value means replaying everything before it, [`first_true_with`] keeps the state
+//! at the lower bound between probes, so each item is replayed only a few times in total.
+
+use std::ops::Range;
+
+use crate::utils::integer::*;
+
+/// The first value in `range` for which `predicate` is true, or `range.end` if there is none.
+#[allow(dead_code)] // no day needs it yet
+pub fn lower_bound<T: Integer<T>>(range: Range<T>, mut predicate: impl FnMut(T) -> bool) -> T {
+    let Range {
+        start: mut low,
+        end: mut high,
+    } = range;
+
+    while low < high {
+        // The floor of the average, without the overflow of `low + high` or of `high - low`
+        // across a signed range.
+        let mid = (low & high) + ((low ^ high) >> T::ONE);
+        match predicate(mid) {
+            true => high = mid,
+            false => low = mid + T::ONE,
+        }
+    }
+
+    low
+}
+
+/// The first value in `range` for which `predicate` is true.
+#[allow(dead_code)] // no day needs it yet
+pub fn first_true<T: Integer<T>>(range: Range<T>, predicate: impl FnMut(T) -> bool) -> Option<T> {
+    let end = range.end;
+    let first = lower_bound(range, predicate);
+    (first != end).then_some(first)
+}
+
+/// The index of the first item in `slice` for which `predicate` is true.
+#[allow(dead_code)] // no day needs it yet
+pub fn first_true_in<T>(slice: &[T], mut predicate: impl FnMut(&T) -> bool) -> Option<usize> {
+    let first = slice.partition_point(|item| !predicate(item));
+    (first != slice.len()).then_some(first)
+}
+
+/// The first index `i` of `items` where `predicate` is true of the state after applying the items
+/// `..=i` to `state` in order with `apply`.
+///
+/// Probes start from a clone of the state at the lower bound, which moves forward whenever a
+/// probe comes back `false`. Each probe covers at most half of what is left, so `apply` is called
+/// fewer than `2 * items.len()` times in all, instead of once per item before every probe.
+#[allow(dead_code)] // no day needs it yet
+pub fn first_true_with<T, S: Clone>(
+    items: &[T],
+    mut state: S,
+    mut apply: impl FnMut(&mut S, &T),
+    mut predicate: impl FnMut(&S) -> bool,
+) -> Option<usize> {
+    let (mut low, mut high) = (0, items.len());
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let mut probe = state.clone();
+        for item in &items[low..=mid] {
+            apply(&mut probe, item);
+        }
+
+        match predicate(&probe) {
+            true => high = mid,
+            false => {
+                state = probe;
+                low = mid + 1;
+            }
+        }
+    }
+
+    (low != items.len()).then_some(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lower_bound() {
+        assert_eq!(lower_bound(0..100_u32, |x| x * x >= 50), 8);
+        assert_eq!(lower_bound(-50..50_i64, |x| x >= -7), -7);
+        assert_eq!(lower_bound(0..10_usize, |_| false), 10);
+        assert_eq!(lower_bound(3..3_usize, |_| true), 3);
+        assert_eq!(lower_bound(200..255_u8, |x| x >= 250), 250);
+        assert_eq!(lower_bound(-30_000..30_000_i16, |x| x >= 29_999), 29_999);
+        assert_eq!(lower_bound(i64::MIN..i64::MAX, |x| x >= -3), -3);
+
+        let mut probes = 0;
+        assert_eq!(
+            first_true(0..1_000_000_u64, |x| {
+                probes += 1;
+                x >= 123_456
+            }),
+            Some(123_456)
+        );
+        assert!(probes <= 20);
+        assert_eq!(first_true(0..10_u8, |x| x > 20), None);
+    }
+
+    #[test]
+    fn test_first_true_in() {
+        let sorted = [1, 3, 3, 5, 8, 13];
+        assert_eq!(first_true_in(&sorted, |&x| x >= 3), Some(1));
+        assert_eq!(first_true_in(&sorted, |&x| x > 5), Some(4));
+        assert_eq!(first_true_in(&sorted, |&x| x > 13), None);
+        assert_eq!(first_true_in(&[] as &[u8], |_| true), None);
+    }
+
+    #[test]
+    fn test_first_true_with() {
+        // The first prefix of the deposits whose balance reaches 100.
+        let deposits = (1..=50).collect::<Vec<u32>>();
+        let mut applied = 0;
+        let first = first_true_with(
+            &deposits,
+            0,
+            |balance, &deposit| {
+                applied += 1;
+                *balance += deposit;
+            },
+            |&balance| balance >= 100,
+        );
+
+        // 1 + 2 + ... + 14 = 105
+        assert_eq!(first, Some(13));
+        assert!(applied <= 2 * deposits.len());
+
+        let never = first_true_with(&deposits, 0, |b, &d| *b += d, |&b| b > 5000);
+        assert_eq!(never, None);
+    }
+
+    #[test]
+    fn test_first_true_with_matches_linear_scan() {
+        let deposits = (1..=50).rev().collect::<Vec<u32>>();
+
+        for target in 0..=1300 {
+            let mut balance = 0;
+            let linear = deposits.iter().position(|&deposit| {
+                balance += deposit;
+                balance >= target
+            });
+
+            let first = first_true_with(&deposits, 0, |b, &d| *b += d, |&b| b >= target);
+            assert_eq!(first, linear, "target {}", target);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Cycle detection for simulations that step from one state to the next.
+//!
+//! A simulation is a starting state and a `step` function. Once a state repeats, every state after
+//! it repeats too, so the whole run is a prefix of [`Cycle::start`] steps followed by a loop of
+//! [`Cycle::length`] steps. [`brent`] finds the cycle in constant memory, [`detect`] remembers
+//! every state it has seen, and [`nth`] uses the cycle to fast-forward to a state like the one
+//! after a billion steps without simulating them all. [`has_cycle`] only answers whether there is
+//! one, which saves the second pass of [`brent`] when the answer is all that matters.
+//!
+//! Simulations that can end, like a guard walking off the map, return `None` from `step`, and the
+//! detectors report no cycle if it does.
+
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the first state that repeats.
+    pub start: usize,
+    /// The number of steps before a state comes around again.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step that reaches the same state as step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        match n < self.start {
+            true => n,
+            false => self.start + (n - self.start) % self.length,
+        }
+    }
+}
+
+/// Brent's algorithm, which compares states but never stores more than two of them.
+#[allow(dead_code)] // no day needs it yet
+pub fn brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let length = cycle_length(initial.clone(), &mut step)?;
+
+    // Then the start, with the hare a whole cycle ahead of the tortoise.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Whether the simulation runs forever, stopping as soon as a repeated state is found.
+pub fn has_cycle<S: Clone + PartialEq>(initial: S, step: impl FnMut(&S) -> Option<S>) -> bool {
+    cycle_length(initial, step).is_some()
+}
+
+/// The first phase of [`brent`], with the hare racing ahead of a tortoise that teleports to it at
+/// each power of two until they meet.
+fn cycle_length<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<usize> {
+    let (mut power, mut length) = (1, 1);
+    let mut hare = step(&initial)?;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    Some(length)
+}
+
+/// Finds the cycle by remembering when each state was first seen, which steps through the prefix
+/// and the cycle only once but keeps every state.
+#[allow(dead_code)] // no day needs it yet
+pub fn detect<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for i in 0.. {
+        if let Some(start) = seen.insert(state.clone(), i) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        state = step(&state)?;
+    }
+
+    unreachable!()
+}
+
+/// The state after `n` steps of a simulation that never ends, skipping whole cycles once the first
+/// repeated state is found.
+#[allow(dead_code)] // no day needs it yet
+pub fn nth<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return states.swap_remove(cycle.index(n));
+        }
+
+        seen.insert(state.clone(), i);
+        states.push(state.clone());
+        state = step(&state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2, 4, 16, 256, 536, ..., 136, 496, 16, 256, ... repeating from step 2 every 20 steps.
+    fn square(x: &u64) -> Option<u64> {
+        Some(x * x % 1000)
+    }
+
+    #[test]
+    fn test_detectors() {
+        let expected = Cycle {
+            start: 2,
+            length: 20,
+        };
+        assert_eq!(brent(2, square), Some(expected));
+        assert_eq!(detect(2, square), Some(expected));
+        assert_eq!(expected.index(1), 1);
+        assert_eq!(expected.index(22), 2);
+        assert_eq!(expected.index(2 + 10 * 20 + 7), 9);
+
+        // Tripling modulo 17 goes through every non-zero value before coming back.
+        let step = |x: &u32| Some(x * 3 % 17);
+        let expected = Cycle {
+            start: 0,
+            length: 16,
+        };
+        assert_eq!(brent(5, step), Some(expected));
+        assert_eq!(detect(5, step), Some(expected));
+
+        // Counting down stops at zero without ever repeating.
+        let countdown = |x: &u8| x.checked_sub(1);
+        assert_eq!(brent(10, countdown), None);
+        assert_eq!(detect(10, countdown), None);
+
+        assert!(has_cycle(2, square));
+        assert!(has_cycle(5, step));
+        assert!(!has_cycle(10, countdown));
+    }
+
+    #[test]
+    fn test_nth() {
+        let step = |x: &u64| square(x).unwrap();
+        let slow = |n| (0..n).fold(2, |x, _| step(&x));
+
+        for n in [0, 1, 3, 10, 57, 1000] {
+            assert_eq!(nth(2, step, n), slow(n));
+        }
+
+        let fast = nth(2, step, 1_000_000_000);
+        let cycle = brent(2, square).unwrap();
+        assert_eq!(fast, slow(cycle.index(1_000_000_000)));
+    }
+}
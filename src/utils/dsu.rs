@@ -0,0 +1,239 @@
+//! A disjoint set union (union-find) with path compression and union by size.
+//!
+//! Sets can only ever be merged, never split, so connectivity problems where cells get blocked
+//! over time are solved backwards: start from the final state and unblock the cells in reverse
+//! order. [`first_disconnecting`] does exactly that for a grid.
+
+use crate::utils::vector_2d::Vector2;
+
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl Dsu {
+    /// `n` elements, each in a set of its own.
+    pub fn new(n: usize) -> Self {
+        Dsu {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// The representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        let (small, large) = match self.size[a] < self.size[b] {
+            true => (a, b),
+            false => (b, a),
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `x`.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The number of disjoint sets.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+/// Which open cells of a grid are connected to each other, through their four neighbours. Cells
+/// start blocked and can only be opened.
+#[derive(Debug, Clone)]
+pub struct GridDsu {
+    pub width: usize,
+    pub height: usize,
+    open: Vec<bool>,
+    dsu: Dsu,
+}
+
+impl GridDsu {
+    pub fn new(width: usize, height: usize) -> Self {
+        GridDsu {
+            width,
+            height,
+            open: vec![false; width * height],
+            dsu: Dsu::new(width * height),
+        }
+    }
+
+    pub fn contains(&self, pos: Vector2) -> bool {
+        (0..self.width as isize).contains(&pos.x) && (0..self.height as isize).contains(&pos.y)
+    }
+
+    pub fn is_open(&self, pos: Vector2) -> bool {
+        self.contains(pos) && self.open[self.index(pos)]
+    }
+
+    /// Opens `pos`, joining it to its open neighbours. Panics if `pos` is off the grid.
+    pub fn open(&mut self, pos: Vector2) {
+        let index = self.index(pos);
+        self.open[index] = true;
+        for next in pos.adjacent_points() {
+            if self.is_open(next) {
+                let next = self.index(next);
+                self.dsu.union(index, next);
+            }
+        }
+    }
+
+    /// Whether there is a path of open cells between `a` and `b`.
+    pub fn connected(&mut self, a: Vector2, b: Vector2) -> bool {
+        if !self.is_open(a) || !self.is_open(b) {
+            return false;
+        }
+        let (a, b) = (self.index(a), self.index(b));
+        self.dsu.connected(a, b)
+    }
+
+    fn index(&self, pos: Vector2) -> usize {
+        match self.contains(pos) {
+            true => pos.row_index() * self.width + pos.column_index(),
+            false => panic!("{} is outside the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+/// Blocks the cells of an open `width` by `height` grid in the order of `blocks`, and returns the
+/// index of the first block after which there is no path from `a` to `b`, or `None` if they stay
+/// connected. Runs in near-linear time by opening the blocks in reverse instead. Panics if a block
+/// is off the grid.
+pub fn first_disconnecting(
+    width: usize,
+    height: usize,
+    blocks: &[Vector2],
+    a: Vector2,
+    b: Vector2,
+) -> Option<usize> {
+    let mut grid = GridDsu::new(width, height);
+
+    // A cell blocked twice only opens up again before its first block.
+    let mut blocked = vec![0_usize; width * height];
+    for &pos in blocks {
+        blocked[grid.index(pos)] += 1;
+    }
+    for pos in (0..height).flat_map(|y| (0..width).map(move |x| Vector2::new_usize(x, y))) {
+        if blocked[grid.index(pos)] == 0 {
+            grid.open(pos);
+        }
+    }
+
+    if grid.connected(a, b) {
+        return None;
+    }
+
+    for (i, &pos) in blocks.iter().enumerate().rev() {
+        let index = grid.index(pos);
+        blocked[index] -= 1;
+        if blocked[index] == 0 {
+            grid.open(pos);
+        }
+        if grid.connected(a, b) {
+            return Some(i);
+        }
+    }
+
+    // `a` or `b` is off the grid, so they were never connected.
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dsu() {
+        let mut dsu = Dsu::new(6);
+        assert_eq!(dsu.sets(), 6);
+
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 4));
+        assert_eq!(dsu.size(2), 4);
+        assert_eq!(dsu.size(5), 1);
+        assert_eq!(dsu.sets(), 3);
+    }
+
+    #[test]
+    fn test_grid() {
+        let mut grid = GridDsu::new(3, 2);
+        let (a, b) = (Vector2::new(0, 0), Vector2::new(2, 0));
+        assert!(!grid.connected(a, b));
+
+        grid.open(a);
+        grid.open(b);
+        grid.open(Vector2::new(0, 1));
+        grid.open(Vector2::new(2, 1));
+        assert!(!grid.connected(a, b));
+
+        grid.open(Vector2::new(1, 1));
+        assert!(grid.connected(a, b));
+        assert!(!grid.is_open(Vector2::new(1, 0)));
+        assert!(!grid.is_open(Vector2::new(3, 0)));
+    }
+
+    #[test]
+    fn test_first_disconnecting() {
+        let cells = |cells: &[(usize, usize)]| {
+            cells
+                .iter()
+                .map(|&(x, y)| Vector2::new_usize(x, y))
+                .collect::<Vec<_>>()
+        };
+        let (a, b) = (Vector2::new(0, 0), Vector2::new(2, 2));
+
+        // The wall down the middle is only complete once `(1, 2)` is blocked.
+        let blocks = cells(&[(1, 0), (2, 0), (1, 1), (1, 1), (1, 2), (0, 2)]);
+        assert_eq!(first_disconnecting(3, 3, &blocks, a, b), Some(4));
+
+        assert_eq!(first_disconnecting(3, 3, &blocks[..4], a, b), None);
+        assert_eq!(first_disconnecting(3, 3, &cells(&[(0, 0)]), a, b), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "(x: 3, y: 0) is outside the 3x3 grid")]
+    fn test_block_off_the_grid() {
+        // Past the end of the first row, which must not wrap onto the second.
+        let (a, b) = (Vector2::new(0, 0), Vector2::new(2, 2));
+        first_disconnecting(3, 3, &[Vector2::new(3, 0)], a, b);
+    }
+}
@@ -1,23 +1,339 @@
-// pub struct Grid<T> {
-//     pub grid: Vec<T>,
-//     pub width: usize,
-//     pub height: usize,
-// }
-
-// impl<T> Grid<T> {
-//     pub fn parse(input: &str) -> Self {
-//         let grid = input
-//             .lines()
-//             .rev()
-//             .map(|l| l.as_bytes())
-//             .collect::<Vec<_>>();
-
-//         let (width, height) = (grid[0].len(), grid.len());
-
-//         Self {
-//             grid,
-//             width,
-//             height,
-//         }
-//     }
-// }
\ No newline at end of file
+//! A rectangular grid of cells stored in one flat `Vec`, addressed by [`Vector2`].
+//!
+//! Every grid knows its [`YAxis`], so whether row `0` is the last line of the input (`Up`) or the
+//! first (`Down`) is decided once, when it is parsed. Rows are stored in order of `y`, so
+//! `cells[y * width + x]` is the cell at `(x, y)`, and [`Display`] draws the grid the way the
+//! input looked either way.
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use crate::etc::error::Error;
+use crate::utils::vector_2d::{Vector2, YAxis};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub cells: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+    pub axis: YAxis,
+}
+
+/// A cell that can be drawn as a single character, used by [`Grid`]'s [`Display`].
+pub trait Cell {
+    fn symbol(&self) -> char;
+}
+
+impl Cell for u8 {
+    fn symbol(&self) -> char {
+        *self as char
+    }
+}
+
+impl Cell for char {
+    fn symbol(&self) -> char {
+        *self
+    }
+}
+
+impl Cell for bool {
+    fn symbol(&self) -> char {
+        match self {
+            true => '#',
+            false => '.',
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of bytes, one row per line.
+    pub fn parse(input: &str, axis: YAxis) -> Result<Self, Error> {
+        Self::parse_with(input, axis, |b| b)
+    }
+}
+
+impl<T> Grid<T> {
+    #[allow(dead_code)] // no day needs it yet
+    pub fn new(width: usize, height: usize, axis: YAxis, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+            axis,
+        }
+    }
+
+    /// Parses a grid one row per line, converting each byte with `f`. Every line must be as long
+    /// as the first.
+    pub fn parse_with(input: &str, axis: YAxis, f: impl Fn(u8) -> T) -> Result<Self, Error> {
+        let mut lines = input.lines().collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
+        if axis == YAxis::Up {
+            lines.reverse();
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            if line.len() != width {
+                let reason = format!("expected {} columns, found {}", width, line.len());
+                return Err(Error::on_line(input, line, reason));
+            }
+            cells.extend(line.bytes().map(&f));
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+            axis,
+        })
+    }
+
+    pub fn contains(&self, pos: Vector2) -> bool {
+        (0..self.width as isize).contains(&pos.x) && (0..self.height as isize).contains(&pos.y)
+    }
+
+    pub fn get(&self, pos: Vector2) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Vector2) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let offset = self.offset(pos);
+        Some(&mut self.cells[offset])
+    }
+
+    /// The `(column, line)` of `pos` in the input, counting lines from the top whichever way the
+    /// grid's y axis points. This is what puzzles mean by "distance from the top edge".
+    pub fn screen_position(&self, pos: Vector2) -> (usize, usize) {
+        let line = self.axis.line(pos.row_index(), self.height);
+        (pos.column_index(), line)
+    }
+
+    /// Every position in the grid, row by row from `y = 0`.
+    pub fn positions(&self) -> impl Iterator<Item = Vector2> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Vector2::new_usize(x, y)))
+    }
+
+    /// The first position, in [`positions`](Self::positions) order, of a cell matching `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Vector2> {
+        self.positions().find(|&pos| predicate(&self[pos]))
+    }
+
+    /// Every position holding `value`.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Vector2> + 'a
+    where
+        T: PartialEq,
+    {
+        self.positions().filter(move |&pos| self[pos] == *value)
+    }
+
+    /// The cells with the given `y`. Panics if there is no such row.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.height,
+            "row {} is outside the {}x{} grid",
+            y,
+            self.width,
+            self.height
+        );
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The rows in order of `y`.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` of an empty slice still needs a non-zero size.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells with the given `x`, in order of `y`. Panics if there is no such column.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} is outside the {}x{} grid",
+            x,
+            self.width,
+            self.height
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The columns from `x = 0` rightwards, each in order of `y`.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            axis: self.axis,
+        }
+    }
+
+    fn offset(&self, pos: Vector2) -> usize {
+        pos.row_index() * self.width + pos.column_index()
+    }
+}
+
+impl<T> Index<Vector2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vector2) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Vector2> for Grid<T> {
+    fn index_mut(&mut self, pos: Vector2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+/// Draws the grid as it appeared in the input, with the top line first.
+impl<T: Cell> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let draw = |f: &mut Formatter<'_>, row: &[T]| {
+            writeln!(f, "{}", row.iter().map(Cell::symbol).collect::<String>())
+        };
+
+        match self.axis {
+            YAxis::Up => self.rows().rev().try_for_each(|row| draw(f, row)),
+            YAxis::Down => self.rows().try_for_each(|row| draw(f, row)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::vector_2d::Direction;
+
+    const INPUT: &str = "#..\n.S#\n..E\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        assert_eq!((grid.width, grid.height), (3, 3));
+        assert_eq!(grid.row(0), b"..E");
+        assert_eq!(grid[Vector2::new(0, 2)], b'#');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let error = Grid::parse("...\n..\n", YAxis::Up).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 3 columns, found 2");
+
+        let empty = Grid::parse("", YAxis::Down).unwrap();
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        assert_eq!(grid.get(Vector2::new(1, 1)), Some(&b'S'));
+        assert_eq!(grid.get(Vector2::new(3, 1)), None);
+        assert_eq!(grid.get(Vector2::new(0, -1)), None);
+
+        grid[Vector2::new(1, 1)] = b'.';
+        *grid.get_mut(Vector2::new(0, 0)).unwrap() = b'#';
+        assert_eq!(grid.to_string(), "#..\n..#\n#.E\n");
+        assert!(grid.get_mut(Vector2::new(-1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_search() {
+        let grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        assert_eq!(grid.find(|&c| c == b'E'), Some(Vector2::new(2, 0)));
+        assert_eq!(grid.find(|&c| c == b'X'), None);
+        assert_eq!(
+            grid.positions_of(&b'#').collect::<Vec<_>>(),
+            vec![Vector2::new(2, 1), Vector2::new(0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        let rows = grid.rows().collect::<Vec<_>>();
+        assert_eq!(rows, vec![b"..E", b".S#", b"#.."]);
+
+        let column = grid.column(2).copied().collect::<Vec<_>>();
+        assert_eq!(column, b"E#.");
+        assert_eq!(grid.columns().count(), 3);
+
+        let walls = grid.map(|&c| c == b'#');
+        assert_eq!(walls.to_string(), "#..\n..#\n...\n");
+
+        let column = grid.column(0).rev().copied().collect::<Vec<_>>();
+        assert_eq!(column, b"#..");
+        let flat = Grid::new(3, 0, YAxis::Up, 0);
+        assert_eq!(flat.column(2).count(), 0);
+        assert_eq!(flat.columns().count(), 3);
+        let thin = Grid::new(0, 2, YAxis::Up, 0);
+        assert_eq!(thin.row(1), &[] as &[i32]);
+        assert_eq!(thin.columns().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside the 3x3 grid")]
+    fn test_column_past_the_edge() {
+        // This used to wrap around into the next row.
+        let grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        grid.column(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3 is outside the 3x3 grid")]
+    fn test_row_past_the_edge() {
+        let grid = Grid::parse(INPUT, YAxis::Up).unwrap();
+        grid.row(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 0 is outside the 0x2 grid")]
+    fn test_column_of_zero_width_grid() {
+        let grid = Grid::new(0, 2, YAxis::Up, 0);
+        grid.column(0).count();
+    }
+
+    #[test]
+    fn test_y_down() {
+        let grid = Grid::parse(INPUT, YAxis::Down).unwrap();
+        assert_eq!(grid.row(0), b"#..");
+        assert_eq!(grid[Vector2::new(0, 0)], b'#');
+        assert_eq!(grid.to_string(), INPUT);
+
+        let up = Grid::parse(INPUT, YAxis::Up).unwrap();
+        let exit = |grid: &Grid<u8>| grid.find(|&c| c == b'E').unwrap();
+        assert_eq!(exit(&grid), Vector2::new(2, 2));
+        assert_eq!(exit(&up), Vector2::new(2, 0));
+        assert_eq!(grid.screen_position(exit(&grid)), (2, 2));
+        assert_eq!(up.screen_position(exit(&up)), (2, 2));
+
+        // Up the screen from the `S` is the `.` above it in both conventions.
+        for grid in [grid, up] {
+            let start = grid.find(|&c| c == b'S').unwrap();
+            assert_eq!(grid[start + grid.axis.up()], b'.');
+            assert_eq!(grid[start + grid.axis.down()], b'.');
+            assert_eq!(grid[start + Direction::E.vector(grid.axis)], b'#');
+        }
+    }
+}
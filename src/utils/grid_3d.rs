@@ -0,0 +1,176 @@
+//! Grids of cells addressed by [`Point3`], either dense over a [`Cuboid`] or sparse.
+//!
+//! [`Grid3`] stores every cell of its bounds in one flat `Vec`, like [`Grid`](super::grid::Grid)
+//! does in 2D, and suits a region that is known up front and mostly filled. [`SparseGrid3`] only
+//! stores the cells that differ from a default, so it can grow in any direction, which suits
+//! cellular automata that spread out over time.
+
+use std::ops::{Index, IndexMut};
+
+use hashbrown::HashMap;
+
+use crate::utils::vector_3d::{Cuboid, Point3};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid3<T> {
+    pub cells: Vec<T>,
+    pub bounds: Cuboid,
+}
+
+#[allow(dead_code)] // no day needs it yet
+impl<T> Grid3<T> {
+    pub fn new(bounds: Cuboid, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid3 {
+            cells: vec![value; bounds.volume()],
+            bounds,
+        }
+    }
+
+    pub fn contains(&self, pos: Point3) -> bool {
+        self.bounds.contains(pos)
+    }
+
+    pub fn get(&self, pos: Point3) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[self.offset(pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: Point3) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+        let offset = self.offset(pos);
+        Some(&mut self.cells[offset])
+    }
+
+    /// Every position in the grid, in the order the cells are stored.
+    pub fn positions(&self) -> impl Iterator<Item = Point3> {
+        self.bounds.points()
+    }
+
+    /// Every position and its cell.
+    pub fn iter(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    fn offset(&self, pos: Point3) -> usize {
+        let (width, height, _) = self.bounds.size();
+        let p = pos - self.bounds.min;
+        (p.z as usize * height + p.y as usize) * width + p.x as usize
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point3) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the grid {:?}", pos, self.bounds),
+        }
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    fn index_mut(&mut self, pos: Point3) -> &mut T {
+        let bounds = self.bounds;
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{} is outside the grid {:?}", pos, bounds),
+        }
+    }
+}
+
+/// A grid without bounds where every cell starts out as `default`.
+#[derive(Debug, Clone)]
+pub struct SparseGrid3<T> {
+    pub cells: HashMap<Point3, T>,
+    pub default: T,
+}
+
+#[allow(dead_code)] // no day needs it yet
+impl<T: Clone + PartialEq> SparseGrid3<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid3 {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn get(&self, pos: Point3) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    /// Sets a cell, forgetting it again when it goes back to the default.
+    pub fn set(&mut self, pos: Point3, value: T) {
+        match value == self.default {
+            true => self.cells.remove(&pos),
+            false => self.cells.insert(pos, value),
+        };
+    }
+
+    /// The smallest box holding every cell that is not the default.
+    pub fn bounds(&self) -> Option<Cuboid> {
+        Cuboid::from_points(self.cells.keys().copied())
+    }
+
+    /// The cells inside `bounds` as a dense grid.
+    pub fn to_dense(&self, bounds: Cuboid) -> Grid3<T> {
+        let mut grid = Grid3::new(bounds, self.default.clone());
+        for (&pos, value) in &self.cells {
+            if let Some(cell) = grid.get_mut(pos) {
+                *cell = value.clone();
+            }
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dense() {
+        let bounds = Cuboid::new(Point3::new(-1, 0, 2), Point3::new(1, 1, 3));
+        let mut grid = Grid3::new(bounds, 0);
+        assert_eq!(grid.cells.len(), 12);
+
+        for (i, pos) in bounds.points().enumerate() {
+            grid[pos] = i;
+        }
+        assert_eq!(grid.cells, (0..12).collect::<Vec<_>>());
+        assert!(grid.iter().all(|(pos, &i)| grid[pos] == i));
+
+        assert_eq!(grid.get(Point3::new(-1, 0, 2)), Some(&0));
+        assert_eq!(grid.get(Point3::new(1, 1, 3)), Some(&11));
+        assert_eq!(grid.get(Point3::new(0, 0, 4)), None);
+        assert!(grid.get_mut(Point3::new(2, 0, 2)).is_none());
+    }
+
+    #[test]
+    fn test_sparse() {
+        let mut grid = SparseGrid3::new(false);
+        assert_eq!(grid.bounds(), None);
+
+        // A plus shape around the origin, then switch off its centre.
+        let centre = Point3::new(0, 0, 0);
+        for pos in centre.adjacent_points() {
+            grid.set(pos, true);
+        }
+        grid.set(centre, true);
+        grid.set(centre, false);
+        assert_eq!(grid.cells.len(), 6);
+        assert!(*grid.get(Point3::new(0, 0, -1)));
+        assert!(!*grid.get(centre));
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(bounds.size(), (3, 3, 3));
+
+        let dense = grid.to_dense(bounds);
+        assert_eq!(dense.cells.iter().filter(|&&on| on).count(), 6);
+        assert!(dense.positions().all(|pos| dense[pos] == *grid.get(pos)));
+    }
+}
@@ -1,5 +1,8 @@
 //! Combines common [operators](https://doc.rust-lang.org/book/appendix-02-operators.html)
 //! and constants `0`, `1` and `10` to enable generic methods on integer types.
+//!
+//! The decimal helpers treat a number as its digits, so `12.concat(345)` is `12345` and
+//! `12345.split_digits(3)` is `(12, 345)`. They are meant for non-negative numbers.
 use std::ops::*;
 
 pub trait Integer<T>:
@@ -22,8 +25,42 @@ pub trait Integer<T>:
     const ONE: T;
     const TEN: T;
 
+    #[allow(dead_code)] // no day needs it yet
     fn ilog2(self) -> T;
+    #[allow(dead_code)] // no day needs it yet
     fn trailing_zeros(self) -> T;
+    /// The largest integer whose square is at most `self`.
+    #[allow(dead_code)] // no day needs it yet
+    fn isqrt(self) -> T;
+    /// `10` raised to `exp`.
+    fn pow10(exp: u32) -> T;
+    /// The number of decimal digits, where `0` has one digit.
+    fn digit_count(self) -> u32;
+    /// The digits of `other` written after the digits of `self`.
+    fn concat(self, other: T) -> T;
+    /// Splits off the last `low` digits, returning the rest and those digits as numbers.
+    fn split_digits(self, low: u32) -> (T, T);
+    /// The decimal digits, most significant first.
+    #[allow(dead_code)] // no day needs it yet
+    fn digits(self) -> Digits<T>;
+}
+
+pub struct Digits<T> {
+    value: T,
+    divisor: T,
+}
+
+impl<T: Integer<T>> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.divisor == T::ZERO {
+            return None;
+        }
+        let digit = self.value / self.divisor % T::TEN;
+        self.divisor = self.divisor / T::TEN;
+        Some(digit)
+    }
 }
 
 pub trait Unsigned<T>: Integer<T> {}
@@ -48,6 +85,37 @@ macro_rules! integer {
             fn trailing_zeros(self) -> $t {
                 <$t>::trailing_zeros(self) as $t
             }
+
+            #[inline]
+            fn isqrt(self) -> $t {
+                <$t>::isqrt(self)
+            }
+
+            #[inline]
+            fn pow10(exp: u32) -> $t {
+                <$t>::pow(10, exp)
+            }
+
+            #[inline]
+            fn digit_count(self) -> u32 {
+                self.checked_ilog10().map_or(1, |log| log + 1)
+            }
+
+            #[inline]
+            fn concat(self, other: $t) -> $t {
+                self * Self::pow10(other.digit_count()) + other
+            }
+
+            #[inline]
+            fn split_digits(self, low: u32) -> ($t, $t) {
+                let divisor = Self::pow10(low);
+                (self / divisor, self % divisor)
+            }
+
+            #[inline]
+            fn digits(self) -> Digits<$t> {
+                Digits { value: self, divisor: Self::pow10(self.digit_count() - 1) }
+            }
         }
     )*)
 }
@@ -58,6 +126,83 @@ macro_rules! empty_trait {
     )*)
 }
 
-integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128);
+integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128 isize);
 empty_trait!(Unsigned for u8 u16 u32 u64 u128 usize);
-empty_trait!(Signed for i16 i32 i64 i128);
\ No newline at end of file
+empty_trait!(Signed for i16 i32 i64 i128 isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The helpers days 7 and 11 used before these methods existed.
+    fn concat_by_log(current: u64, next: u64) -> u64 {
+        current * 10u64.pow(next.ilog10() + 1) + next
+    }
+
+    fn split_by_log(value: u64) -> (u64, u64) {
+        let digits = value.ilog10() + 1;
+        let divisor = 10u64.pow(digits / 2);
+        (value / divisor, value % divisor)
+    }
+
+    #[test]
+    fn test_digit_count() {
+        assert_eq!(0_u32.digit_count(), 1);
+        assert_eq!(9_u8.digit_count(), 1);
+        assert_eq!(10_i32.digit_count(), 2);
+        assert_eq!(u64::MAX.digit_count(), 20);
+
+        for value in 1..100_000_u64 {
+            assert_eq!(value.digit_count(), value.ilog10() + 1);
+            assert_eq!(value.digit_count(), value.to_string().len() as u32);
+        }
+    }
+
+    #[test]
+    fn test_concat_and_split() {
+        assert_eq!(12_u64.concat(345), 12345);
+        assert_eq!(12_u64.concat(0), 120);
+        assert_eq!(12345_u64.split_digits(3), (12, 345));
+        assert_eq!(1000_u32.split_digits(2), (10, 0));
+
+        for a in 0..300_u64 {
+            for b in 1..300 {
+                let joined = a.concat(b);
+                assert_eq!(joined, concat_by_log(a, b));
+                assert_eq!(joined.split_digits(b.digit_count()), (a, b));
+            }
+        }
+
+        for value in (1..1_000_000_u64).filter(|v| v.digit_count() % 2 == 0) {
+            assert_eq!(
+                value.split_digits(value.digit_count() / 2),
+                split_by_log(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(9051_u32.digits().collect::<Vec<_>>(), vec![9, 0, 5, 1]);
+        assert_eq!(0_u8.digits().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(u64::MAX.digits().count(), 20);
+
+        for value in 0..100_000_u64 {
+            let digits = value.digits().fold(0, |n, digit| n * 10 + digit);
+            assert_eq!(digits, value);
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(0_u32.isqrt(), 0);
+        assert_eq!(15_i64.isqrt(), 3);
+        assert_eq!(16_u8.isqrt(), 4);
+        assert_eq!(u64::MAX.isqrt(), u32::MAX as u64);
+
+        for value in 0..100_000_u64 {
+            let root = Integer::isqrt(value);
+            assert!(root * root <= value && value < (root + 1) * (root + 1));
+        }
+    }
+}
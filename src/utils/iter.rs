@@ -9,6 +9,7 @@ pub struct Chunk<I: Iterator, const N: usize> {
     iter: I,
 }
 
+#[allow(dead_code)] // no day needs it yet
 pub trait ChunkOps: Iterator + Sized {
     fn chunk<const N: usize>(self) -> Chunk<Self, N>;
 }
@@ -0,0 +1,407 @@
+//! Exact solutions of integer linear systems, using [`Rational`] arithmetic so that nothing is
+//! lost to truncating division.
+//!
+//! [`solve`] runs Gaussian elimination and says whether there is one solution, none, or a whole
+//! family of them. Puzzles usually want whole, non-negative answers like button presses, which
+//! [`cheapest_natural`] picks out, including from a family of solutions when the system is
+//! degenerate.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::utils::math::{gcd, lcm};
+
+/// A fraction kept in lowest terms, with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "{}/0 is not a rational", numerator);
+
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub const fn integer(value: i128) -> Self {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn floor(self) -> i128 {
+        self.numerator.div_euclid(self.denominator)
+    }
+
+    pub fn ceil(self) -> i128 {
+        -(-self).floor()
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational::integer(value)
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numerator: -self.numerator,
+            ..self
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Both denominators are positive, so cross-multiplying keeps the order.
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solutions {
+    Unique(Vec<Rational>),
+    None,
+    /// Every `particular + t1 * directions[0] + t2 * directions[1] + ...` is a solution. Each
+    /// direction belongs to a free variable, which is `1` in it and `0` in `particular`.
+    Infinite {
+        particular: Vec<Rational>,
+        directions: Vec<Vec<Rational>>,
+    },
+}
+
+/// Solves `matrix * x = rhs`, where every row of `matrix` has one coefficient per variable.
+/// Panics unless `rhs` has a value for each row and the rows are all the same length.
+pub fn solve(matrix: &[Vec<i128>], rhs: &[i128]) -> Solutions {
+    assert_eq!(matrix.len(), rhs.len(), "one right-hand side value per row");
+    let variables = matrix.first().map_or(0, Vec::len);
+    assert!(
+        matrix.iter().all(|row| row.len() == variables),
+        "every row needs {} coefficients",
+        variables
+    );
+    let mut rows = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| {
+            row.iter()
+                .chain([&value])
+                .map(|&x| Rational::integer(x))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Reduce to row echelon form with every pivot 1 and alone in its column.
+    let mut pivots = Vec::new();
+    for column in 0..variables {
+        let row = pivots.len();
+        let Some(pivot) = (row..rows.len()).find(|&r| rows[r][column] != Rational::ZERO) else {
+            continue;
+        };
+        rows.swap(row, pivot);
+
+        let scale = rows[row][column];
+        rows[row].iter_mut().for_each(|x| *x = *x / scale);
+        let pivot_row = rows[row].clone();
+        for (other, values) in rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if other != row && factor != Rational::ZERO {
+                for (x, &p) in values.iter_mut().zip(&pivot_row).skip(column) {
+                    *x = *x - factor * p;
+                }
+            }
+        }
+        pivots.push(column);
+    }
+
+    // A row of zeros left equal to something else is `0 = 1`.
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| row[variables] != Rational::ZERO)
+    {
+        return Solutions::None;
+    }
+
+    let mut particular = vec![Rational::ZERO; variables];
+    for (row, &column) in pivots.iter().enumerate() {
+        particular[column] = rows[row][variables];
+    }
+
+    let directions = (0..variables)
+        .filter(|column| !pivots.contains(column))
+        .map(|free| {
+            let mut direction = vec![Rational::ZERO; variables];
+            direction[free] = Rational::ONE;
+            for (row, &column) in pivots.iter().enumerate() {
+                direction[column] = -rows[row][free];
+            }
+            direction
+        })
+        .collect::<Vec<_>>();
+
+    match directions.is_empty() {
+        true => Solutions::Unique(particular),
+        false => Solutions::Infinite {
+            particular,
+            directions,
+        },
+    }
+}
+
+/// The whole, non-negative solution of `matrix * x = rhs` with the lowest total `costs * x`,
+/// returned as that cost and the solution.
+///
+/// A family of solutions is searched when it has a single free variable, which covers a pair of
+/// collinear buttons. More free variables than that are not supported and give `None`, as do a
+/// family with no lowest cost and a system with no whole, non-negative solution.
+pub fn cheapest_natural(
+    matrix: &[Vec<i128>],
+    rhs: &[i128],
+    costs: &[i128],
+) -> Option<(i128, Vec<i128>)> {
+    let natural = |x: &[Rational]| -> Option<(i128, Vec<i128>)> {
+        let x = x
+            .iter()
+            .map(|v| v.to_integer().filter(|&v| v >= 0))
+            .collect::<Option<Vec<_>>>()?;
+        Some((x.iter().zip(costs).map(|(x, c)| x * c).sum(), x))
+    };
+
+    let (particular, direction) = match solve(matrix, rhs) {
+        Solutions::Unique(x) => return natural(&x),
+        Solutions::None => return None,
+        Solutions::Infinite {
+            particular,
+            directions,
+        } => match <[_; 1]>::try_from(directions) {
+            Ok([direction]) => (particular, direction),
+            Err(_) => return None,
+        },
+    };
+
+    // x(t) = particular + t * direction, where t is the free variable so must be whole and
+    // non-negative. Every other variable that moves with t bounds it from one side.
+    let (mut low, mut high) = (0, None::<i128>);
+    for (&p, &d) in particular.iter().zip(&direction) {
+        match d.cmp(&Rational::ZERO) {
+            Ordering::Greater => low = low.max((-p / d).ceil()),
+            Ordering::Less => high = Some(high.unwrap_or(i128::MAX).min((-p / d).floor())),
+            Ordering::Equal if p < Rational::ZERO => return None,
+            Ordering::Equal => {}
+        }
+    }
+
+    // Which t give whole values repeats with the period of the direction's denominators, so one
+    // period from the cheap end finds the cheapest whole solution if there is one.
+    let period = direction.iter().map(|d| d.denominator()).fold(1, lcm);
+    let slope = direction
+        .iter()
+        .zip(costs)
+        .map(|(&d, &c)| d * Rational::integer(c))
+        .fold(Rational::ZERO, Add::add);
+    let at = |t: i128| {
+        let x = particular
+            .iter()
+            .zip(&direction)
+            .map(|(&p, &d)| p + Rational::integer(t) * d)
+            .collect::<Vec<_>>();
+        natural(&x)
+    };
+
+    match (slope >= Rational::ZERO, high) {
+        (_, Some(high)) if high < low => None,
+        (true, high) => (low..=high.unwrap_or(i128::MAX).min(low + period - 1)).find_map(at),
+        (false, Some(high)) => (low.max(high - period + 1)..=high).rev().find_map(at),
+        (false, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rational(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator)
+    }
+
+    #[test]
+    fn test_rational() {
+        assert_eq!(rational(6, -4), rational(-3, 2));
+        assert_eq!(rational(0, -5), Rational::ZERO);
+        assert_eq!(rational(1, 3) + rational(1, 6), rational(1, 2));
+        assert_eq!(rational(1, 3) - rational(1, 2), rational(-1, 6));
+        assert_eq!(rational(2, 3) * rational(9, 4), rational(3, 2));
+        assert_eq!(rational(2, 3) / rational(-4, 9), rational(-3, 2));
+        assert!(rational(-1, 2) < rational(1, 3));
+
+        assert_eq!((rational(7, 2).floor(), rational(7, 2).ceil()), (3, 4));
+        assert_eq!((rational(-7, 2).floor(), rational(-7, 2).ceil()), (-4, -3));
+        assert_eq!(rational(8, 2).to_integer(), Some(4));
+        assert_eq!(rational(7, 2).to_integer(), None);
+        assert_eq!(rational(-7, 2).to_string(), "-7/2");
+        assert_eq!(Rational::from(5).to_string(), "5");
+    }
+
+    #[test]
+    fn test_solve() {
+        // The first claw machine from day 13.
+        let machine = [vec![94, 22], vec![34, 67]];
+        let integers = |x: &[i128]| x.iter().map(|&x| Rational::integer(x)).collect::<Vec<_>>();
+        assert_eq!(
+            solve(&machine, &[8400, 5400]),
+            Solutions::Unique(integers(&[80, 40]))
+        );
+        assert_eq!(
+            solve(&machine, &[1, 0]),
+            Solutions::Unique(vec![rational(67, 5550), rational(-34, 5550)])
+        );
+
+        let collinear = [vec![2, 4], vec![3, 6]];
+        assert_eq!(solve(&collinear, &[10, 16]), Solutions::None);
+        assert_eq!(
+            solve(&collinear, &[10, 15]),
+            Solutions::Infinite {
+                particular: integers(&[5, 0]),
+                directions: vec![integers(&[-2, 1])],
+            }
+        );
+
+        let three = [vec![1, 1, 1], vec![0, 2, 5], vec![2, 5, -1]];
+        assert_eq!(
+            solve(&three, &[6, -4, 27]),
+            Solutions::Unique(integers(&[5, 3, -2]))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "one right-hand side value per row")]
+    fn test_solve_missing_rhs() {
+        solve(&[vec![1, 2], vec![3, 4]], &[5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "every row needs 2 coefficients")]
+    fn test_solve_ragged_rows() {
+        solve(&[vec![1, 2], vec![3]], &[5, 6]);
+    }
+
+    #[test]
+    fn test_cheapest_natural() {
+        let costs = [3, 1];
+        let machine = [vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            cheapest_natural(&machine, &[8400, 5400], &costs),
+            Some((280, vec![80, 40]))
+        );
+        assert_eq!(cheapest_natural(&machine, &[8401, 5400], &costs), None);
+
+        // Button A moves 3 times as far as B. When it costs more than 3 times as much only B is
+        // pressed, and when it costs less A is pressed as often as a whole number of presses of
+        // B allows.
+        let collinear = [vec![3, 1], vec![6, 2]];
+        assert_eq!(
+            cheapest_natural(&collinear, &[10, 20], &[4, 1]),
+            Some((10, vec![0, 10]))
+        );
+        assert_eq!(
+            cheapest_natural(&collinear, &[10, 20], &[2, 1]),
+            Some((7, vec![3, 1]))
+        );
+
+        // Both buttons move an even distance, so an odd prize is out of reach.
+        let even = [vec![2, 4], vec![4, 8]];
+        assert_eq!(cheapest_natural(&even, &[5, 10], &costs), None);
+        assert_eq!(
+            cheapest_natural(&even, &[6, 12], &[1, 1]),
+            Some((2, vec![1, 1]))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Number theory on any [`Integer`]: greatest common divisors, modular arithmetic and the Chinese
+//! remainder theorem.
+//!
+//! Nothing here checks for overflow. Modular products need room for the square of the modulus,
+//! so use `i128` or `u128` when the moduli are beyond 32 bits.
+
+use crate::utils::integer::*;
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is `0`.
+pub fn gcd<T: Integer<T>>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// The least common multiple, which is never negative. It is `0` if either number is.
+pub fn lcm<T: Integer<T>>(a: T, b: T) -> T {
+    match a == T::ZERO || b == T::ZERO {
+        true => T::ZERO,
+        false => abs(a / gcd(a, b) * b),
+    }
+}
+
+/// The gcd `g` of `a` and `b` together with `x` and `y` such that `a * x + b * y == g`.
+#[allow(dead_code)] // no day needs it yet
+pub fn extended_gcd<T: Signed<T>>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    match r0 < T::ZERO {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    }
+}
+
+/// `a` modulo a positive `m`, in `0..m` even when `a` is negative.
+#[allow(dead_code)] // no day needs it yet
+pub fn modulo<T: Integer<T>>(a: T, m: T) -> T {
+    let r = a % m;
+    match r < T::ZERO {
+        true => r + m,
+        false => r,
+    }
+}
+
+/// `base` raised to a non-negative `exp`, modulo a positive `m`. Panics if `exp` is negative.
+#[allow(dead_code)] // no day needs it yet
+pub fn pow_mod<T: Integer<T>>(base: T, exp: T, m: T) -> T {
+    assert!(exp >= T::ZERO, "pow_mod needs a non-negative exponent");
+    let (mut base, mut exp) = (modulo(base, m), exp);
+    let mut result = modulo(T::ONE, m);
+
+    while exp != T::ZERO {
+        if exp & T::ONE == T::ONE {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp = exp >> T::ONE;
+    }
+
+    result
+}
+
+/// The `x` in `0..m` with `a * x` congruent to `1` modulo `m`, if `a` and `m` are coprime.
+#[allow(dead_code)] // no day needs it yet
+pub fn mod_inverse<T: Signed<T>>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    (g == T::ONE).then(|| modulo(x, m))
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)` at once, returning the smallest non-negative `x` and
+/// the modulus it is unique for. The moduli need not be coprime, and `None` means the
+/// congruences contradict each other.
+#[allow(dead_code)] // no day needs it yet
+pub fn crt<T: Signed<T>>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut solution = (T::ZERO, T::ONE);
+
+    for &(r2, m2) in congruences {
+        let (r1, m1) = solution;
+        let (g, x, _) = extended_gcd(m1, m2);
+        let difference = r2 - r1;
+        if difference % g != T::ZERO {
+            return None;
+        }
+
+        // r1 + m1 * k satisfies both once m1 * k ≡ r2 - r1 (mod m2).
+        let step = m2 / g;
+        let k = modulo(difference / g % step * modulo(x, step), step);
+        let m = m1 * step;
+        solution = (modulo(r1 + m1 * k, m), m);
+    }
+
+    Some(solution)
+}
+
+fn abs<T: Integer<T>>(a: T) -> T {
+    match a < T::ZERO {
+        true => T::ZERO - a,
+        false => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12_u32, 18), 6);
+        assert_eq!(gcd(-12_i64, 18), 6);
+        assert_eq!(gcd(17_u64, 5), 1);
+        assert_eq!(gcd(0_u8, 7), 7);
+        assert_eq!(gcd(0_i32, 0), 0);
+
+        assert_eq!(lcm(4_u64, 6), 12);
+        assert_eq!(lcm(-4_i32, 6), 12);
+        assert_eq!(lcm(0_usize, 6), 0);
+        assert_eq!([3_u64, 4, 5, 6].into_iter().fold(1, lcm), 60);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for a in -30_i64..30 {
+            for b in -30..30 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(g, gcd(a, b));
+                assert_eq!(a * x + b * y, g);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(modulo(-7_i32, 3), 2);
+        assert_eq!(modulo(7_u32, 3), 1);
+
+        assert_eq!(pow_mod(2_u64, 10, 1000), 24);
+        assert_eq!(pow_mod(3_i64, 0, 7), 1);
+        assert_eq!(pow_mod(5_u32, 3, 1), 0);
+        assert_eq!(pow_mod(-2_i64, 3, 5), 2);
+        // Fermat's little theorem.
+        assert_eq!(pow_mod(123_456_u128, 1_000_000_006, 1_000_000_007), 1);
+
+        for m in 2_i64..40 {
+            for a in 0..m {
+                match mod_inverse(a, m) {
+                    Some(inverse) => assert_eq!(a * inverse % m, 1),
+                    None => assert_ne!(gcd(a, m), 1),
+                }
+            }
+        }
+        assert_eq!(mod_inverse(-3_i32, 7), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "pow_mod needs a non-negative exponent")]
+    fn test_pow_mod_negative_exponent() {
+        pow_mod(2_i64, -1, 7);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2_i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1_i64, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(1_i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<i32>(&[]), Some((0, 1)));
+
+        // Every combination of remainders for coprime moduli has exactly one solution.
+        for r1 in 0_i64..4 {
+            for r2 in 0..9 {
+                let (x, m) = crt(&[(r1, 4), (r2, 9)]).unwrap();
+                assert_eq!((x % 4, x % 9, m), (r1, r2, 36));
+            }
+        }
+    }
+}
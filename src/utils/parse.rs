@@ -39,7 +39,9 @@ pub struct ParseSigned<'a, T> {
 }
 
 pub trait ParseOps {
+    #[allow(dead_code)] // no day needs it yet
     fn unsigned<T: Unsigned<T>>(&self) -> T;
+    #[allow(dead_code)] // no day needs it yet
     fn signed<T: Signed<T>>(&self) -> T;
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T>;
     fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T>;
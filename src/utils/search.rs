@@ -0,0 +1,543 @@
+//! Breadth-first, depth-first, Dijkstra and A* search over a state space described by [`Space`].
+//!
+//! A day implements [`Space`] for its input, usually with a position (and maybe a facing) as the
+//! state, and picks the search that fits: [`bfs`] when every step costs the same, [`dijkstra`]
+//! when steps have different costs, [`astar`] when a good lower bound to the goal is known, and
+//! [`dfs`] when any path will do. Every search stops at the first goal state it settles; a space
+//! with no goal states is searched exhaustively, which is how to get the distance to everything.
+//!
+//! When every optimal path matters rather than just one, [`all_shortest_paths`] keeps all the
+//! equally good predecessors of each state, and stops only once no goal can be reached as cheaply.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+use hashbrown::{HashMap, HashSet};
+
+pub trait Space {
+    type State: Clone + Eq + Hash;
+
+    fn start(&self) -> Self::State;
+
+    /// The states one step away from `state`, with the cost of each step.
+    fn successors(&self, state: &Self::State) -> impl IntoIterator<Item = (Self::State, usize)>;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// An estimate of the cost from `state` to the nearest goal, used by [`astar`]. It must be
+    /// consistent: zero at a goal, and never dropping by more than the cost of a step. That keeps
+    /// it from overestimating, and lets A* settle each state once without ever reopening it. The
+    /// default of zero makes A* behave like Dijkstra.
+    #[allow(dead_code)] // no day needs it yet
+    fn heuristic(&self, _state: &Self::State) -> usize {
+        0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    #[allow(dead_code)] // no day needs it yet
+    pub start: S,
+    /// The goal state that was reached, if any.
+    pub goal: Option<S>,
+    /// The cost of reaching every state the search got to. [`bfs`] records a state as soon as it
+    /// is discovered, so this includes states still queued when the goal was found, while the
+    /// others only record the states they visited. These are shortest distances, except for
+    /// [`dfs`] where they are the cost along the path it happened to take.
+    pub distances: HashMap<S, usize>,
+    parents: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Search {
+            distances: HashMap::from([(start.clone(), 0)]),
+            start,
+            goal: None,
+            parents: HashMap::new(),
+        }
+    }
+
+    /// The cost of reaching the goal.
+    pub fn distance(&self) -> Option<usize> {
+        self.distances.get(self.goal.as_ref()?).copied()
+    }
+
+    /// The states from the start to the goal, both included.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// The states from the start to `state`, both included, if `state` is in [`distances`](Self::distances).
+    #[allow(dead_code)] // no day needs it yet
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Searches in order of the number of steps, ignoring the step costs.
+pub fn bfs<P: Space>(space: &P) -> Search<P::State> {
+    let mut search = Search::new(space.start());
+    let mut queue = VecDeque::from([space.start()]);
+
+    while let Some(state) = queue.pop_front() {
+        if space.is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        let steps = search.distances[&state] + 1;
+        for (next, _) in space.successors(&state) {
+            if !search.distances.contains_key(&next) {
+                search.distances.insert(next.clone(), steps);
+                search.parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Follows each branch as deep as it goes before backtracking. The path found is not the shortest.
+#[allow(dead_code)] // no day needs it yet
+pub fn dfs<P: Space>(space: &P) -> Search<P::State> {
+    // A state is only visited when popped, so it hangs off the deepest branch that reached it.
+    let mut search = Search {
+        distances: HashMap::new(),
+        ..Search::new(space.start())
+    };
+    let mut stack = vec![(space.start(), None, 0)];
+
+    while let Some((state, parent, cost)) = stack.pop() {
+        if search.distances.contains_key(&state) {
+            continue;
+        }
+        search.distances.insert(state.clone(), cost);
+        if let Some(parent) = parent {
+            search.parents.insert(state.clone(), parent);
+        }
+
+        if space.is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in space.successors(&state) {
+            if !search.distances.contains_key(&next) {
+                stack.push((next, Some(state.clone()), cost + step));
+            }
+        }
+    }
+
+    search
+}
+
+/// Searches in order of cost.
+pub fn dijkstra<P: Space>(space: &P) -> Search<P::State> {
+    best_first(space, |_| 0)
+}
+
+/// Searches in order of cost plus [`Space::heuristic`].
+#[allow(dead_code)] // no day needs it yet
+pub fn astar<P: Space>(space: &P) -> Search<P::State> {
+    best_first(space, |state| space.heuristic(state))
+}
+
+fn best_first<P: Space>(space: &P, heuristic: impl Fn(&P::State) -> usize) -> Search<P::State> {
+    let start = space.start();
+    // Unlike bfs, a state only gets its distance once it is settled.
+    let mut search = Search {
+        distances: HashMap::new(),
+        ..Search::new(start.clone())
+    };
+
+    let mut costs: HashMap<_, _> = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Entry {
+        estimate: heuristic(&start),
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        if search.distances.contains_key(&state) {
+            continue;
+        }
+        search.distances.insert(state.clone(), cost);
+
+        if space.is_goal(&state) {
+            search.goal = Some(state);
+            break;
+        }
+
+        for (next, step) in space.successors(&state) {
+            let cost = cost + step;
+            if costs.get(&next).is_some_and(|&best| best <= cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), cost);
+            search.parents.insert(next.clone(), state.clone());
+            queue.push(Entry {
+                estimate: cost + heuristic(&next),
+                cost,
+                state: next,
+            });
+        }
+    }
+
+    search
+}
+
+/// Every shortest path from the start to the goals, as found by [`all_shortest_paths`].
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    #[allow(dead_code)] // no day needs it yet
+    pub start: S,
+    /// The goal states reached at the lowest cost, in the order they were settled.
+    pub goals: Vec<S>,
+    /// The shortest distance to every state the search settled.
+    pub distances: HashMap<S, usize>,
+    /// For each settled state but the start, every state it can be reached from on a shortest
+    /// path. Together they form a DAG of all the optimal paths.
+    pub predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> Paths<S> {
+    /// The cost of reaching the goals.
+    pub fn distance(&self) -> Option<usize> {
+        self.distances.get(self.goals.first()?).copied()
+    }
+
+    /// Every state on some shortest path to a goal, the start and goals included.
+    pub fn states(&self) -> HashSet<S> {
+        let mut states = self.goals.iter().cloned().collect::<HashSet<_>>();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if states.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+
+        states
+    }
+
+    /// Every shortest path to a goal, each from the start to the goal. There can be exponentially
+    /// many, so prefer [`states`](Self::states) when only the states matter.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = Vec::new();
+        let mut stack = self
+            .goals
+            .iter()
+            .map(|goal| vec![goal.clone()])
+            .collect::<Vec<_>>();
+
+        while let Some(path) = stack.pop() {
+            match self.predecessors.get(path.last().unwrap()) {
+                Some(previous) => stack.extend(previous.iter().map(|previous| {
+                    let mut path = path.clone();
+                    path.push(previous.clone());
+                    path
+                })),
+                None => paths.push(path.into_iter().rev().collect()),
+            }
+        }
+
+        paths
+    }
+}
+
+/// Searches in order of cost like [`dijkstra`], keeping every predecessor that reaches a state at
+/// its shortest distance, and carries on until all the goals at the lowest cost are settled.
+pub fn all_shortest_paths<P: Space>(space: &P) -> Paths<P::State> {
+    let start = space.start();
+    let mut paths = Paths {
+        start: start.clone(),
+        goals: Vec::new(),
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+
+    let mut costs: HashMap<_, _> = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Entry {
+        estimate: 0,
+        cost: 0,
+        state: start,
+    }]);
+
+    while let Some(Entry { cost, state, .. }) = queue.pop() {
+        if paths.distance().is_some_and(|best| best < cost) {
+            break;
+        }
+        if paths.distances.contains_key(&state) {
+            continue;
+        }
+        paths.distances.insert(state.clone(), cost);
+
+        if space.is_goal(&state) {
+            paths.goals.push(state);
+            continue;
+        }
+
+        for (next, step) in space.successors(&state) {
+            let cost = cost + step;
+            match costs.get(&next) {
+                Some(&best) if best < cost => continue,
+                Some(&best) if best == cost => {
+                    paths
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(state.clone());
+                    continue;
+                }
+                _ => {}
+            }
+
+            costs.insert(next.clone(), cost);
+            paths.predecessors.insert(next.clone(), vec![state.clone()]);
+            queue.push(Entry {
+                estimate: cost,
+                cost,
+                state: next,
+            });
+        }
+    }
+
+    // States queued but never settled before the search stopped are not on any shortest path.
+    paths
+        .predecessors
+        .retain(|state, _| paths.distances.contains_key(state));
+    paths
+}
+
+/// A queued state, ordered so that [`BinaryHeap`] pops the lowest estimate first.
+struct Entry<S> {
+    estimate: usize,
+    cost: usize,
+    state: S,
+}
+
+impl<S> PartialEq for Entry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for Entry<S> {}
+
+impl<S> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Prefer the deeper of two equal estimates, which reaches the goal sooner with A*.
+        (other.estimate, self.cost).cmp(&(self.estimate, other.cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+    use crate::utils::vector_2d::{Vector2, YAxis};
+
+    const MAZE: &str = "\
+S#...
+.#.#.
+...~E
+";
+
+    struct Maze {
+        grid: Grid<u8>,
+        /// The cost of stepping onto a `~`.
+        swamp: usize,
+    }
+
+    impl Maze {
+        fn new(input: &str, swamp: usize) -> Self {
+            let grid = Grid::parse(input, YAxis::Down).unwrap();
+            Maze { grid, swamp }
+        }
+    }
+
+    impl Space for Maze {
+        type State = Vector2;
+
+        fn start(&self) -> Vector2 {
+            self.grid.find(|&c| c == b'S').unwrap()
+        }
+
+        fn successors(&self, &state: &Vector2) -> impl IntoIterator<Item = (Vector2, usize)> {
+            state
+                .adjacent_points()
+                .into_iter()
+                .filter_map(|next| match self.grid.get(next)? {
+                    b'#' => None,
+                    b'~' => Some((next, self.swamp)),
+                    _ => Some((next, 1)),
+                })
+        }
+
+        fn is_goal(&self, state: &Vector2) -> bool {
+            self.grid[*state] == b'E'
+        }
+
+        fn heuristic(&self, state: &Vector2) -> usize {
+            let exit = self.grid.find(|&c| c == b'E').unwrap();
+            state.distance_to(exit)
+        }
+    }
+
+    fn check_path(maze: &Maze, search: &Search<Vector2>) {
+        let path = search.path().unwrap();
+        assert_eq!(path.first(), Some(&maze.start()));
+        assert!(maze.is_goal(path.last().unwrap()));
+        for pair in path.windows(2) {
+            assert_eq!(pair[0].distance_to(pair[1]), 1);
+        }
+    }
+
+    #[test]
+    fn test_unit_costs() {
+        let maze = Maze::new(MAZE, 1);
+
+        for search in [bfs(&maze), dijkstra(&maze), astar(&maze)] {
+            assert_eq!(search.distance(), Some(6));
+            assert_eq!(search.path().unwrap().len(), 7);
+            check_path(&maze, &search);
+        }
+
+        let search = dfs(&maze);
+        assert!(search.distance().unwrap() >= 6);
+        check_path(&maze, &search);
+    }
+
+    #[test]
+    fn test_weighted_costs() {
+        // The short way runs through a swamp, the long way around is dry.
+        let maze = Maze::new(MAZE, 10);
+
+        assert_eq!(bfs(&maze).distance(), Some(6));
+        for search in [dijkstra(&maze), astar(&maze)] {
+            assert_eq!(search.distance(), Some(10));
+            assert_eq!(search.path().unwrap().len(), 11);
+            check_path(&maze, &search);
+        }
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let walled = MAZE.replace("~E", "#E").replace(".#.#.", ".#.##");
+        let maze = Maze::new(&walled, 1);
+
+        for search in [bfs(&maze), dfs(&maze), dijkstra(&maze), astar(&maze)] {
+            assert_eq!(search.goal, None);
+            assert_eq!(search.distance(), None);
+            assert_eq!(search.path(), None);
+            // Everything reachable was searched, the `E` corner was not.
+            assert_eq!(search.distances.len(), 9);
+        }
+
+        let search = bfs(&maze);
+        assert_eq!(search.distances[&Vector2::new(4, 0)], 8);
+        assert_eq!(search.path_to(&Vector2::new(2, 2)).unwrap().len(), 5);
+    }
+
+    /// A directed graph on numbered states, with an optional goal.
+    struct Graph(&'static [(u8, u8)], Option<u8>);
+
+    impl Space for Graph {
+        type State = u8;
+
+        fn start(&self) -> u8 {
+            0
+        }
+
+        fn successors(&self, &state: &u8) -> impl IntoIterator<Item = (u8, usize)> {
+            self.0
+                .iter()
+                .filter(move |&&(from, _)| from == state)
+                .map(|&(_, to)| (to, 1))
+        }
+
+        fn is_goal(&self, &state: &u8) -> bool {
+            self.1 == Some(state)
+        }
+    }
+
+    #[test]
+    fn test_dfs_goes_deep() {
+        // 0 leads to both 1 and 2, and 2 leads to 1 as well.
+        let graph = Graph(&[(0, 1), (0, 2), (2, 1)], None);
+
+        assert_eq!(bfs(&graph).path_to(&1), Some(vec![0, 1]));
+        // Depth first, 1 is reached through 2 before the shallow edge is backtracked to.
+        let search = dfs(&graph);
+        assert_eq!(search.path_to(&1), Some(vec![0, 2, 1]));
+        assert_eq!(search.distances[&1], 2);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        // With a swamp of 5 the short way and the long way cost the same.
+        let maze = Maze::new(MAZE, 5);
+        let paths = all_shortest_paths(&maze);
+
+        assert_eq!(paths.distance(), Some(10));
+        assert_eq!(paths.goals, vec![Vector2::new(4, 2)]);
+        assert_eq!(paths.states().len(), 12);
+
+        let mut lengths = paths.paths().iter().map(Vec::len).collect::<Vec<_>>();
+        lengths.sort();
+        assert_eq!(lengths, vec![7, 11]);
+        assert!(paths.paths().iter().all(|path| path[0] == maze.start()));
+        assert!(paths
+            .predecessors
+            .keys()
+            .all(|state| paths.distances.contains_key(state)));
+
+        // An open 3x3 room has six shortest paths across it, and every tile is on one of them.
+        let room = Maze::new("S..\n...\n..E\n", 1);
+        let paths = all_shortest_paths(&room);
+        assert_eq!(paths.distance(), Some(4));
+        assert_eq!(paths.paths().len(), 6);
+        assert_eq!(paths.states().len(), 9);
+
+        let walled = MAZE.replace("~E", "#E").replace(".#.#.", ".#.##");
+        let paths = all_shortest_paths(&Maze::new(&walled, 1));
+        assert_eq!(paths.distance(), None);
+        assert!(paths.states().is_empty());
+        assert!(paths.paths().is_empty());
+    }
+
+    #[test]
+    fn test_all_shortest_paths_unsettled() {
+        // 3 is queued behind 2 but the search stops at the goal 1 before settling it.
+        let graph = Graph(&[(0, 1), (0, 2), (2, 3)], Some(1));
+        let paths = all_shortest_paths(&graph);
+
+        assert_eq!(paths.goals, vec![1]);
+        assert!(!paths.distances.contains_key(&3));
+        assert!(!paths.predecessors.contains_key(&3));
+        assert_eq!(paths.predecessors[&2], vec![0]);
+    }
+}
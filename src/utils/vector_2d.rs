@@ -1,14 +1,15 @@
-#![allow(dead_code)]
-
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
+use crate::utils::integer::*;
+
 pub const ORIGIN: Vector2 = Vector2 { x: 0, y: 0 };
 
 pub const DIRS: [Vector2; 8] = [N, NE, E, SE, S, SW, W, NW];
 pub const DIAGONALS: [Vector2; 4] = [NW, NE, SE, SW];
+#[allow(dead_code)] // no day needs it yet
 pub const CARDINALS: [Vector2; 4] = [N, E, S, W];
 
 pub const N: Vector2 = Vector2 { x: 0, y: 1 };
@@ -20,23 +21,258 @@ pub const SW: Vector2 = Vector2 { x: -1, y: -1 };
 pub const SE: Vector2 = Vector2 { x: 1, y: -1 };
 pub const NW: Vector2 = Vector2 { x: -1, y: 1 };
 
+/// Which way `y` grows on screen, i.e. in the puzzle input.
+///
+/// The compass constants are fixed vectors: [`N`] is always `(0, 1)`. With [`YAxis::Up`] the last
+/// line of the input is `y = 0` and [`N`] points up the screen. With [`YAxis::Down`] the first line
+/// is `y = 0`, as in puzzles that count rows from the top, and [`N`] points down the screen.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum YAxis {
+    #[default]
+    Up,
+    Down,
+}
+
+impl YAxis {
+    /// The direction pointing up the screen.
+    #[allow(dead_code)] // no day needs it yet
+    pub const fn up(self) -> Vector2 {
+        match self {
+            YAxis::Up => N,
+            YAxis::Down => S,
+        }
+    }
+
+    /// The direction pointing down the screen.
+    #[allow(dead_code)] // no day needs it yet
+    pub const fn down(self) -> Vector2 {
+        self.up().inverse()
+    }
+
+    /// The line of the input, counted from the top, that row `y` of a grid `height` lines tall
+    /// came from.
+    pub const fn line(self, y: usize, height: usize) -> usize {
+        match self {
+            YAxis::Up => height - 1 - y,
+            YAxis::Down => y,
+        }
+    }
+}
+
+/// One of the eight compass directions, as seen on screen: [`Direction::N`] is always up.
+///
+/// Turning a `Direction` cannot fail, unlike matching on [`Vector2`] constants. It only becomes a
+/// vector through [`Direction::vector`], which needs the grid's [`YAxis`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::N,
+        Direction::NE,
+        Direction::E,
+        Direction::SE,
+        Direction::S,
+        Direction::SW,
+        Direction::W,
+        Direction::NW,
+    ];
+    #[allow(dead_code)] // no day needs it yet
+    pub const CARDINALS: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+    #[allow(dead_code)] // no day needs it yet
+    pub const DIAGONALS: [Direction; 4] =
+        [Direction::NE, Direction::SE, Direction::SW, Direction::NW];
+
+    /// Parses an arrow (`^>v<`), a compass letter (`NESW`) or a move (`URDL`).
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'^' | b'N' | b'U' => Some(Direction::N),
+            b'>' | b'E' | b'R' => Some(Direction::E),
+            b'v' | b'S' | b'D' => Some(Direction::S),
+            b'<' | b'W' | b'L' => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    /// The arrow drawing a cardinal direction.
+    #[allow(dead_code)] // no day needs it yet
+    pub const fn arrow(self) -> Option<char> {
+        match self {
+            Direction::N => Some('^'),
+            Direction::E => Some('>'),
+            Direction::S => Some('v'),
+            Direction::W => Some('<'),
+            _ => None,
+        }
+    }
+
+    /// The position in [`Direction::ALL`], handy for indexing per-direction arrays.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub const fn is_cardinal(self) -> bool {
+        self.index().is_multiple_of(2)
+    }
+
+    /// Rotates clockwise by `eighths` of a full turn, or anticlockwise when negative.
+    pub const fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self.index() as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub const fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub const fn turn_around(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// The unit step in this direction on a grid whose y axis points `axis`.
+    pub const fn vector(self, axis: YAxis) -> Vector2 {
+        let (x, up) = match self {
+            Direction::N => (0, 1),
+            Direction::NE => (1, 1),
+            Direction::E => (1, 0),
+            Direction::SE => (1, -1),
+            Direction::S => (0, -1),
+            Direction::SW => (-1, -1),
+            Direction::W => (-1, 0),
+            Direction::NW => (-1, 1),
+        };
+        let y = match axis {
+            YAxis::Up => up,
+            YAxis::Down => -up,
+        };
+        Vector2 { x, y }
+    }
+
+    /// The direction of a unit step, including diagonal ones, on a grid whose y axis points `axis`.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn from_vector(vector: Vector2, axis: YAxis) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.vector(axis) == vector)
+    }
+}
+
+/// A point or offset on a 2D grid. The coordinates are `isize` unless a smaller type is wanted,
+/// like `Vector2<u16>` for compact keys in a large set. The compass constants, turning and the
+/// other helpers that need negative steps are only for the default `isize`.
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Vector2 {
-    pub x: isize,
-    pub y: isize,
+pub struct Vector2<T = isize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Display for Vector2 {
+impl<T: Display> Display for Vector2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "(x: {}, y: {})", self.x, self.y)
     }
 }
 
-impl Vector2 {
-    pub(crate) fn new(x: isize, y: isize) -> Vector2 {
+impl<T: Integer<T>> Vector2<T> {
+    pub const fn new(x: T, y: T) -> Self {
         Vector2 { x, y }
     }
 
+    /// Converts to another coordinate type, or `None` if a coordinate does not fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vector2<U>> {
+        Some(Vector2 {
+            x: U::try_from(self.x).ok()?,
+            y: U::try_from(self.y).ok()?,
+        })
+    }
+
+    /// Converts to another coordinate type, panicking if a coordinate does not fit.
+    pub fn cast<U: TryFrom<T>>(self) -> Vector2<U> {
+        match self.try_cast() {
+            Some(v) => v,
+            None => panic!(
+                "coordinates out of range for {}",
+                std::any::type_name::<U>()
+            ),
+        }
+    }
+
+    /// The `(column, row)` for indexing a `Vec`, or `None` if either is negative.
+    pub fn index(&self) -> Option<(usize, usize)>
+    where
+        usize: TryFrom<T>,
+    {
+        let Vector2 { x, y } = self.try_cast::<usize>()?;
+        Some((x, y))
+    }
+
+    /// The column for indexing a `Vec`. Panics if `x` is negative.
+    pub fn column_index(&self) -> usize
+    where
+        usize: TryFrom<T>,
+    {
+        usize::try_from(self.x).unwrap_or_else(|_| panic!("negative column"))
+    }
+
+    /// The row for indexing a `Vec`. Panics if `y` is negative.
+    pub fn row_index(&self) -> usize
+    where
+        usize: TryFrom<T>,
+    {
+        usize::try_from(self.y).unwrap_or_else(|_| panic!("negative row"))
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub fn manhattan_distance(&self) -> usize
+    where
+        usize: TryFrom<T>,
+    {
+        self.distance_to(Vector2::new(T::ZERO, T::ZERO))
+    }
+
+    pub fn distance_to(&self, other: Self) -> usize
+    where
+        usize: TryFrom<T>,
+    {
+        let difference = |a: T, b: T| match a < b {
+            true => b - a,
+            false => a - b,
+        };
+        Vector2::new(difference(self.x, other.x), difference(self.y, other.y))
+            .index()
+            .map(|(x, y)| x + y)
+            .expect("distance out of range for usize")
+    }
+
+    /// The up to four points next to this one that lie in `0..width` and `0..height`, which
+    /// never steps below zero even for unsigned coordinates.
+    pub fn adjacent_within(&self, width: T, height: T) -> impl Iterator<Item = Self> {
+        let Vector2 { x, y } = *self;
+        [
+            (x > T::ZERO).then(|| Vector2::new(x - T::ONE, y)),
+            (x + T::ONE < width).then(|| Vector2::new(x + T::ONE, y)),
+            (y > T::ZERO).then(|| Vector2::new(x, y - T::ONE)),
+            (y + T::ONE < height).then(|| Vector2::new(x, y + T::ONE)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[allow(dead_code)] // no day needs it yet
+impl Vector2 {
     pub const fn new_usize(x: usize, y: usize) -> Self {
         Vector2 {
             x: x as isize,
@@ -52,55 +288,44 @@ impl Vector2 {
         self.x
     }
 
-    pub fn column_index(&self) -> usize {
-        self.x as usize
-    }
-
     pub fn row(&self) -> isize {
         self.y
     }
 
-    pub fn row_index(&self) -> usize {
-        self.y as usize
-    }
-
-    pub fn manhattan_distance(&self) -> usize {
-        self.x.unsigned_abs() + self.y.unsigned_abs()
-    }
-
-    pub fn distance_to(&self, other: Vector2) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
-    }
-
-    pub fn inverse(&self) -> Self {
+    pub const fn inverse(&self) -> Self {
         Vector2 {
             x: -self.x,
             y: -self.y,
         }
     }
 
+    /// The point to the right of `self` when facing `direction`, with [`YAxis::Up`].
     pub fn right(&self, direction: Vector2) -> Self {
-        let dir = match direction {
-            N => E,
-            E => S,
-            S => W,
-            W => N,
-            _ => unreachable!(),
-        };
-
-        *self + dir
+        *self + direction.turn_right(YAxis::Up)
     }
 
+    /// The point to the left of `self` when facing `direction`, with [`YAxis::Up`].
     pub fn left(&self, direction: Vector2) -> Self {
-        let dir = match direction {
-            N => W,
-            W => S,
-            S => E,
-            E => N,
-            _ => unreachable!(),
-        };
+        *self + direction.turn_left(YAxis::Up)
+    }
+
+    /// Rotates a quarter turn clockwise as seen on screen.
+    pub const fn turn_right(&self, axis: YAxis) -> Self {
+        match axis {
+            YAxis::Up => Vector2 {
+                x: self.y,
+                y: -self.x,
+            },
+            YAxis::Down => Vector2 {
+                x: -self.y,
+                y: self.x,
+            },
+        }
+    }
 
-        *self + dir
+    /// Rotates a quarter turn anticlockwise as seen on screen.
+    pub const fn turn_left(&self, axis: YAxis) -> Self {
+        self.turn_right(axis).inverse()
     }
 
     pub fn north(&self) -> Vector2 {
@@ -123,12 +348,6 @@ impl Vector2 {
         self.x.unsigned_abs().max(self.y.unsigned_abs())
     }
 
-    pub(crate) fn wrap(&self, max_x: usize, max_y: usize) -> Vector2 {
-        let x = self.x.rem_euclid(max_x as isize);
-        let y = self.y.rem_euclid(max_y as isize);
-        Vector2 { x, y }
-    }
-
     pub(crate) fn cross(&self, vel_b: Vector2) -> Vector2 {
         Vector2 {
             x: self.y * vel_b.y - self.x * vel_b.x,
@@ -147,10 +366,10 @@ impl TryFrom<(usize, usize)> for Vector2 {
     }
 }
 
-impl Add<Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Add<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: Vector2) -> Self::Output {
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -158,68 +377,46 @@ impl Add<Vector2> for Vector2 {
     }
 }
 
-impl Add<&Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Add<&Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: &Vector2) -> Self::Output {
-        Vector2 {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Add<&(isize, isize)> for Vector2 {
-    type Output = Vector2;
-
-    fn add(self, rhs: &(isize, isize)) -> Self::Output {
-        Vector2 {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
-        }
+    fn add(self, rhs: &Vector2<T>) -> Self::Output {
+        self + *rhs
     }
 }
 
-impl Add<(isize, isize)> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Add<(T, T)> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: (isize, isize)) -> Self::Output {
-        Vector2 {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
-        }
+    fn add(self, rhs: (T, T)) -> Self::Output {
+        self + Vector2::new(rhs.0, rhs.1)
     }
 }
 
-impl Add<(usize, usize)> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Add<&(T, T)> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn add(self, rhs: (usize, usize)) -> Self::Output {
-        Vector2 {
-            x: self.x + rhs.0 as isize,
-            y: self.y + rhs.1 as isize,
-        }
+    fn add(self, rhs: &(T, T)) -> Self::Output {
+        self + *rhs
     }
 }
 
-impl AddAssign<Vector2> for Vector2 {
-    fn add_assign(&mut self, rhs: Vector2) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+impl<T: Integer<T>> AddAssign<Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, rhs: Vector2<T>) {
+        *self = *self + rhs;
     }
 }
 
-impl AddAssign<&Vector2> for Vector2 {
-    fn add_assign(&mut self, rhs: &Vector2) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+impl<T: Integer<T>> AddAssign<&Vector2<T>> for Vector2<T> {
+    fn add_assign(&mut self, rhs: &Vector2<T>) {
+        *self = *self + rhs;
     }
 }
 
-impl Sub<Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Sub<Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, rhs: Vector2) -> Self::Output {
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
         Vector2 {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
@@ -227,65 +424,314 @@ impl Sub<Vector2> for Vector2 {
     }
 }
 
-impl Sub<&Vector2> for Vector2 {
-    type Output = Vector2;
+impl<T: Integer<T>> Sub<&Vector2<T>> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn sub(self, rhs: &Vector2) -> Self::Output {
-        Vector2 {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+    fn sub(self, rhs: &Vector2<T>) -> Self::Output {
+        self - *rhs
     }
 }
 
-impl SubAssign<Vector2> for Vector2 {
-    fn sub_assign(&mut self, rhs: Vector2) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+impl<T: Integer<T>> SubAssign<Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: Vector2<T>) {
+        *self = *self - rhs;
     }
 }
 
-impl SubAssign<&Vector2> for Vector2 {
-    fn sub_assign(&mut self, rhs: &Vector2) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+impl<T: Integer<T>> SubAssign<&Vector2<T>> for Vector2<T> {
+    fn sub_assign(&mut self, rhs: &Vector2<T>) {
+        *self = *self - rhs;
     }
 }
 
-impl std::ops::Mul<usize> for Vector2 {
-    type Output = Self;
+impl<T: Integer<T>> Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn mul(self, rhs: usize) -> Self::Output {
-        self * rhs as isize
+    fn mul(self, rhs: T) -> Self::Output {
+        Vector2 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
     }
 }
 
-impl std::ops::Mul<isize> for Vector2 {
-    type Output = Self;
+impl<T: Signed<T>> Neg for Vector2<T> {
+    type Output = Vector2<T>;
 
-    fn mul(self, rhs: isize) -> Self::Output {
+    fn neg(self) -> Self::Output {
         Vector2 {
-            x: self.x * rhs,
-            y: self.y * rhs,
+            x: -self.x,
+            y: -self.y,
         }
     }
 }
 
-impl std::ops::Mul<f64> for Vector2 {
-    type Output = (f64, f64);
+/// The rectangle of points from `min` to `max`, both included. It is empty when `max` is below
+/// `min` on either axis.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Rect {
+    pub min: Vector2,
+    pub max: Vector2,
+}
+
+impl Rect {
+    pub const fn new(min: Vector2, max: Vector2) -> Self {
+        Rect { min, max }
+    }
+
+    /// The `width` by `height` rectangle with its corner at the origin.
+    pub const fn from_size(width: usize, height: usize) -> Self {
+        Rect::new(
+            ORIGIN,
+            Vector2::new(width as isize - 1, height as isize - 1),
+        )
+    }
+
+    /// The smallest rectangle holding every point, or `None` if there are none.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn from_points(points: impl IntoIterator<Item = Vector2>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::new(first, first), |bounds, p| bounds.including(p)))
+    }
+
+    /// The smallest rectangle holding this one and `point`.
+    pub fn including(&self, point: Vector2) -> Self {
+        self.union(&Rect::new(point, point))
+    }
+
+    /// The smallest rectangle holding both.
+    pub fn union(&self, other: &Rect) -> Self {
+        Rect::new(
+            Vector2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Vector2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// The points in both, or `None` if they do not overlap.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn intersect(&self, other: &Rect) -> Option<Self> {
+        let overlap = Rect::new(
+            Vector2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Vector2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        );
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn contains(&self, point: Vector2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
 
-    fn mul(self, rhs: f64) -> (f64, f64) {
-        (self.x as f64 * rhs, self.y as f64 * rhs)
+    /// The number of points along x and y.
+    pub fn size(&self) -> (usize, usize) {
+        let extent = |min: isize, max: isize| (max - min + 1).max(0) as usize;
+        (
+            extent(self.min.x, self.max.x),
+            extent(self.min.y, self.max.y),
+        )
+    }
+
+    #[allow(dead_code)] // no day needs it yet
+    pub fn area(&self) -> usize {
+        let (width, height) = self.size();
+        width * height
+    }
+
+    /// The point inside the rectangle closest to `point`. Panics if the rectangle is empty.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn clamp(&self, point: Vector2) -> Vector2 {
+        Vector2::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+        )
+    }
+
+    /// Moves `point` into the rectangle as if its edges wrapped around, like a torus. Panics if
+    /// the rectangle is empty.
+    pub fn wrap(&self, point: Vector2) -> Vector2 {
+        let (width, height) = self.size();
+        let offset = point - self.min;
+        self.min
+            + Vector2::new(
+                offset.x.rem_euclid(width as isize),
+                offset.y.rem_euclid(height as isize),
+            )
+    }
+
+    /// Every point in the rectangle, in order of y, then x.
+    #[allow(dead_code)] // no day needs it yet
+    pub fn points(&self) -> impl Iterator<Item = Vector2> {
+        let Rect { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vector2::new(x, y)))
+    }
+
+    /// The four corner quarters, in the same order as [`Rect::points`] would reach them. An odd
+    /// width or height leaves out the middle column or row, which belongs to no quarter.
+    pub fn quadrants(&self) -> [Rect; 4] {
+        let (width, height) = self.size();
+        let (half_width, half_height) = ((width / 2) as isize, (height / 2) as isize);
+        let Rect { min, max } = *self;
+
+        let low_x = (min.x, min.x + half_width - 1);
+        let high_x = (max.x - half_width + 1, max.x);
+        let low_y = (min.y, min.y + half_height - 1);
+        let high_y = (max.y - half_height + 1, max.y);
+
+        [
+            (low_x, low_y),
+            (high_x, low_y),
+            (low_x, high_y),
+            (high_x, high_y),
+        ]
+        .map(|((min_x, max_x), (min_y, max_y))| {
+            Rect::new(Vector2::new(min_x, min_y), Vector2::new(max_x, max_y))
+        })
     }
 }
 
-impl std::ops::Mul<i32> for Vector2 {
-    type Output = Self;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_y_axis() {
+        for axis in [YAxis::Up, YAxis::Down] {
+            let up = axis.up();
+            assert_eq!(up.turn_right(axis), E);
+            assert_eq!(E.turn_right(axis), axis.down());
+            assert_eq!(up.turn_left(axis), W);
+            assert_eq!(W.turn_left(axis), axis.down());
+
+            for dir in Direction::ALL {
+                let vector = dir.vector(axis);
+                assert_eq!(Direction::from_vector(vector, axis), Some(dir));
+                assert_eq!(dir.turn_right().vector(axis), vector.turn_right(axis));
+                assert_eq!(dir.turn_left().vector(axis), vector.turn_left(axis));
+            }
+            assert_eq!(Direction::N.vector(axis), axis.up());
+        }
 
-    fn mul(self, rhs: i32) -> Self::Output {
-        Vector2 {
-            x: self.x * rhs as isize,
-            y: self.y * rhs as isize,
+        assert_eq!(YAxis::Up.line(0, 5), 4);
+        assert_eq!(YAxis::Down.line(0, 5), 0);
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+        assert_eq!(Direction::NE.turn_right(), Direction::SE);
+        assert_eq!(Direction::SW.turn_around(), Direction::NE);
+        assert_eq!(Direction::NW.rotate(1), Direction::N);
+        assert_eq!(Direction::N.rotate(-9), Direction::NW);
+
+        for (i, byte) in "^>v<".bytes().enumerate() {
+            let dir = Direction::from_byte(byte).unwrap();
+            assert_eq!(dir, Direction::CARDINALS[i]);
+            assert_eq!(dir.arrow(), Some(byte as char));
+            assert_eq!(Direction::from_byte(b"NESW"[i]), Some(dir));
+            assert_eq!(Direction::from_byte(b"URDL"[i]), Some(dir));
+        }
+        assert_eq!(Direction::from_byte(b'x'), None);
+        assert_eq!(Direction::NE.arrow(), None);
+
+        assert!(Direction::CARDINALS.iter().all(|d| d.is_cardinal()));
+        assert!(!Direction::DIAGONALS.iter().any(|d| d.is_cardinal()));
+        assert_eq!(Direction::from_vector(Vector2::new(2, 0), YAxis::Up), None);
+        assert_eq!(Direction::SE.vector(YAxis::Up), SE);
+        assert_eq!(Direction::SE.vector(YAxis::Down), NE);
+    }
+
+    #[test]
+    fn test_coordinate_types() {
+        let small = Vector2::<u16>::new(3, 4);
+        assert_eq!(small + Vector2::new(1, 1), Vector2::new(4, 5));
+        assert_eq!(small * 2, Vector2::new(6, 8));
+        assert_eq!(small.distance_to(Vector2::new(5, 1)), 5);
+        assert_eq!(small.manhattan_distance(), 7);
+        assert_eq!(small.index(), Some((3, 4)));
+
+        let wide: Vector2 = small.cast();
+        assert_eq!(wide, Vector2::new(3, 4));
+        assert_eq!(-wide, Vector2::new(-3, -4));
+        assert_eq!((-wide).try_cast::<u16>(), None);
+        assert_eq!(Vector2::<i32>::new(300, 0).try_cast::<u8>(), None);
+        assert_eq!((-wide).index(), None);
+        assert_eq!(Vector2::new(-3_i32, 2).manhattan_distance(), 5);
+
+        // No steps off the edge, even where unsigned coordinates would wrap.
+        let corner = Vector2::<u8>::new(0, 0);
+        let mut adjacent = corner.adjacent_within(3, 3).collect::<Vec<_>>();
+        adjacent.sort();
+        assert_eq!(adjacent, vec![Vector2::new(0, 1), Vector2::new(1, 0)]);
+        assert_eq!(Vector2::<u8>::new(1, 1).adjacent_within(3, 3).count(), 4);
+        assert_eq!(Vector2::<u8>::new(2, 2).adjacent_within(3, 3).count(), 2);
+    }
+
+    #[test]
+    fn test_rect() {
+        let points = [Vector2::new(1, 2), Vector2::new(-1, 4), Vector2::new(0, 0)];
+        let bounds = Rect::from_points(points).unwrap();
+        assert_eq!(bounds, Rect::new(Vector2::new(-1, 0), Vector2::new(1, 4)));
+        assert_eq!(bounds.size(), (3, 5));
+        assert_eq!(bounds.area(), 15);
+        assert_eq!(bounds.points().count(), 15);
+        assert!(bounds.points().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(Vector2::new(2, 2)));
+        assert_eq!(Rect::from_points([]), None);
+
+        let grid = Rect::from_size(3, 2);
+        assert_eq!(grid.max, Vector2::new(2, 1));
+        assert_eq!(
+            bounds.intersect(&grid),
+            Some(Rect::new(Vector2::new(0, 0), Vector2::new(1, 1)))
+        );
+        assert_eq!(grid.intersect(&Rect::from_size(0, 5)), None);
+        assert_eq!(
+            bounds.union(&grid),
+            Rect::new(Vector2::new(-1, 0), Vector2::new(2, 4))
+        );
+        assert!(Rect::from_size(0, 5).is_empty());
+
+        assert_eq!(grid.clamp(Vector2::new(-4, 7)), Vector2::new(0, 1));
+        assert_eq!(grid.clamp(Vector2::new(1, 1)), Vector2::new(1, 1));
+        assert_eq!(grid.wrap(Vector2::new(-1, 5)), Vector2::new(2, 1));
+        assert_eq!(bounds.wrap(Vector2::new(2, -1)), Vector2::new(-1, 4));
+    }
+
+    #[test]
+    fn test_quadrants() {
+        // The middle column and row of an odd-sized grid are left out.
+        let quadrants = Rect::from_size(5, 3).quadrants();
+        assert_eq!(
+            quadrants[0],
+            Rect::new(Vector2::new(0, 0), Vector2::new(1, 0))
+        );
+        assert_eq!(
+            quadrants[3],
+            Rect::new(Vector2::new(3, 2), Vector2::new(4, 2))
+        );
+        assert_eq!(quadrants.map(|q| q.area()), [2; 4]);
+
+        // An even-sized one splits exactly.
+        let bounds = Rect::new(Vector2::new(-2, 1), Vector2::new(1, 4));
+        let quadrants = bounds.quadrants();
+        assert_eq!(
+            quadrants.iter().map(Rect::area).sum::<usize>(),
+            bounds.area()
+        );
+        for p in bounds.points() {
+            assert_eq!(quadrants.iter().filter(|q| q.contains(p)).count(), 1);
         }
+        assert_eq!(
+            quadrants[1],
+            Rect::new(Vector2::new(0, 1), Vector2::new(1, 2))
+        );
+
+        assert!(Rect::from_size(1, 1).quadrants().iter().all(Rect::is_empty));
     }
-}
\ No newline at end of file
+}
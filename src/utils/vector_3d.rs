@@ -1,6 +1,11 @@
-#![allow(dead_code)]
+//! Points in three dimensions.
+//!
+//! [`Vector3`] stores `f64` for geometry like [`cross`](Vector3::cross) and
+//! [`normalize`](Vector3::normalize). Voxels and 3D cellular automata want [`Point3`] instead,
+//! which is exact and can be a set or map key, with [`Cuboid`] to bound a group of them.
 
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 pub struct Vector3 {
@@ -9,6 +14,7 @@ pub struct Vector3 {
     pub z: f64,
 }
 
+#[allow(dead_code)] // no day needs it yet
 impl Vector3 {
     pub const fn new(x: f64, y: f64, z: f64) -> Vector3 {
         Vector3 { x, y, z }
@@ -201,3 +207,262 @@ impl std::ops::Mul<f64> for Vector3 {
         }
     }
 }
+
+/// A point on the integer lattice.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+#[allow(dead_code)] // no day needs it yet
+pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+#[allow(dead_code)] // no day needs it yet
+impl Point3 {
+    pub const fn new(x: isize, y: isize, z: isize) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    pub const fn new_usize(x: usize, y: usize, z: usize) -> Self {
+        Point3 {
+            x: x as isize,
+            y: y as isize,
+            z: z as isize,
+        }
+    }
+
+    /// The 6 points sharing a face with this one.
+    pub fn adjacent_points(&self) -> [Point3; 6] {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .map(|(x, y, z)| *self + Point3::new(x, y, z))
+    }
+
+    /// The 26 points sharing a face, edge or corner with this one.
+    pub fn neighbours(&self) -> [Point3; 26] {
+        let mut neighbours = [*self; 26];
+        let offsets = (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z))))
+            .filter(|&offset| offset != ORIGIN);
+        for (neighbour, offset) in neighbours.iter_mut().zip(offsets) {
+            *neighbour += offset;
+        }
+        neighbours
+    }
+
+    pub fn manhattan_distance(&self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+    }
+
+    pub fn distance_to(&self, other: Point3) -> usize {
+        (*self - other).manhattan_distance()
+    }
+
+    /// The lowest of each coordinate.
+    pub fn component_min(&self, other: Point3) -> Point3 {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// The highest of each coordinate.
+    pub fn component_max(&self, other: Point3) -> Point3 {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(x: {}, y: {}, z: {})", self.x, self.y, self.z)
+    }
+}
+
+impl From<Point3> for Vector3 {
+    fn from(point: Point3) -> Vector3 {
+        Vector3::new_isize(point.x, point.y, point.z)
+    }
+}
+
+impl Add<Point3> for Point3 {
+    type Output = Point3;
+
+    fn add(self, rhs: Point3) -> Self::Output {
+        Point3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl AddAssign<Point3> for Point3 {
+    fn add_assign(&mut self, rhs: Point3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Point3> for Point3 {
+    type Output = Point3;
+
+    fn sub(self, rhs: Point3) -> Self::Output {
+        Point3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl SubAssign<Point3> for Point3 {
+    fn sub_assign(&mut self, rhs: Point3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Point3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Self::Output {
+        self * -1
+    }
+}
+
+/// The box of points from `min` to `max`, both included.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+#[allow(dead_code)] // no day needs it yet
+impl Cuboid {
+    pub const fn new(min: Point3, max: Point3) -> Self {
+        Cuboid { min, max }
+    }
+
+    /// The smallest box holding every point, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Point3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Cuboid::new(first, first), |bounds, p| bounds.including(p)))
+    }
+
+    /// The smallest box holding this one and `point`.
+    pub fn including(&self, point: Point3) -> Self {
+        Cuboid::new(self.min.component_min(point), self.max.component_max(point))
+    }
+
+    /// The box grown by `amount` on every side.
+    pub fn expand(&self, amount: isize) -> Self {
+        let amount = Point3::new(amount, amount, amount);
+        Cuboid::new(self.min - amount, self.max + amount)
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// The number of points along x, y and z.
+    pub fn size(&self) -> (usize, usize, usize) {
+        let extent = |min: isize, max: isize| (max - min + 1).max(0) as usize;
+        (
+            extent(self.min.x, self.max.x),
+            extent(self.min.y, self.max.y),
+            extent(self.min.z, self.max.z),
+        )
+    }
+
+    pub fn volume(&self) -> usize {
+        let (x, y, z) = self.size();
+        x * y * z
+    }
+
+    /// Every point in the box, in order of z, then y, then x.
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let Cuboid { min, max } = *self;
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(-4, 5, 0);
+        assert_eq!(a + b, Point3::new(-3, 3, 3));
+        assert_eq!(a - b, Point3::new(5, -7, 3));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+        assert_eq!(a.manhattan_distance(), 6);
+        assert_eq!(a.distance_to(b), 15);
+        assert_eq!(Vector3::from(a), Vector3::new(1.0, -2.0, 3.0));
+
+        let adjacent = a.adjacent_points();
+        assert!(adjacent.iter().all(|&p| p.distance_to(a) == 1));
+
+        let neighbours = a.neighbours();
+        assert!(!neighbours.contains(&a));
+        assert!(neighbours
+            .iter()
+            .all(|&p| (p - a).x.abs() <= 1 && (p - a).z.abs() <= 1));
+        assert!(adjacent.iter().all(|p| neighbours.contains(p)));
+        let unique = neighbours.iter().collect::<std::collections::HashSet<_>>();
+        assert_eq!(unique.len(), 26);
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let points = [
+            Point3::new(1, 2, 3),
+            Point3::new(-1, 4, 3),
+            Point3::new(0, 0, 5),
+        ];
+        let bounds = Cuboid::from_points(points).unwrap();
+        assert_eq!(
+            bounds,
+            Cuboid::new(Point3::new(-1, 0, 3), Point3::new(1, 4, 5))
+        );
+        assert_eq!(bounds.size(), (3, 5, 3));
+        assert_eq!(bounds.volume(), 45);
+        assert_eq!(bounds.points().count(), 45);
+        assert!(bounds.points().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(Point3::new(2, 2, 4)));
+
+        let grown = bounds.expand(1);
+        assert!(grown.contains(Point3::new(2, 2, 4)));
+        assert_eq!(grown.volume(), 5 * 7 * 5);
+        assert_eq!(Cuboid::from_points([]), None);
+    }
+}